@@ -0,0 +1,135 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation and keeping track of the peak heap usage.
+/// Installed as the global allocator with the `alloc-stats` feature, for the binary as well as
+/// the tests.
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    COUNT.fetch_add(1, Relaxed);
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+/// Heap usage of a single phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocs {
+    /// Number of allocations, reallocations included
+    pub count: usize,
+    /// Highest number of bytes allocated on top of what was allocated before the phase started
+    pub peak_bytes: usize,
+}
+
+impl Allocs {
+    /// Combines the usage of a phase run over several inputs one after the other
+    pub fn merge(self, other: Allocs) -> Allocs {
+        Allocs {
+            count: self.count + other.count,
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+        }
+    }
+}
+
+impl Display for Allocs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocs, ", self.count)?;
+        match self.peak_bytes {
+            b if b < 1024 => write!(f, "{b} B peak"),
+            b if b < 1024 * 1024 => write!(f, "{:.1} KiB peak", b as f64 / 1024.0),
+            b => write!(f, "{:.1} MiB peak", b as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// Heap usage of the phases of a day, `None` for phases that weren't run or when allocations
+/// aren't being counted
+pub type PhaseAllocs = [Option<Allocs>; 3];
+
+/// Runs `f`, counting its allocations when the counting allocator is installed. The counters are
+/// global, so allocations of anything running alongside, e.g. other days with `--parallel`, are
+/// counted too.
+#[inline(always)]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocs>) {
+    if !cfg!(feature = "alloc-stats") {
+        return (f(), None);
+    }
+    let count = COUNT.load(Relaxed);
+    let base = CURRENT.load(Relaxed);
+    PEAK.store(base, Relaxed);
+    let result = f();
+    let allocs = Allocs {
+        count: COUNT.load(Relaxed) - count,
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(base),
+    };
+    (result, Some(allocs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let allocs = Allocs {
+            count: 3,
+            peak_bytes: 1536,
+        };
+        assert_eq!(allocs.to_string(), "3 allocs, 1.5 KiB peak");
+        let merged = allocs.merge(Allocs {
+            count: 2,
+            peak_bytes: 100,
+        });
+        assert_eq!(merged.to_string(), "5 allocs, 1.5 KiB peak");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (v, allocs) = measure(|| vec![0u64; 1024]);
+        assert_eq!(v.len(), 1024);
+        // Other tests allocate at the same time, so only the count is reliable here
+        let counted = allocs.map(|a| a.count >= 1);
+        assert_eq!(counted, cfg!(feature = "alloc-stats").then_some(true));
+    }
+}
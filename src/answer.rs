@@ -0,0 +1,149 @@
+use crate::sections::Value;
+use num::BigInt;
+use std::fmt::{Display, Formatter};
+
+/// The answer to a part of a puzzle, kept typed so answers compare exactly
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    /// Integers that don't fit an `i64`, smaller ones are always stored as `Integer`
+    BigInteger(BigInt),
+    /// Passwords, coordinates, comma-separated lists and the like
+    Text(String),
+    /// Rows of ASCII art to read the answer from
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Builds a grid answer from its rows
+    pub fn grid<R: Into<String>>(rows: impl IntoIterator<Item = R>) -> Answer {
+        Answer::Grid(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Guesses the type of an answer written out as text, e.g. the example answers of a day. Only
+    /// integers in their canonical form are read as integers, so text like `007` stays text.
+    pub fn parse(s: &str) -> Answer {
+        if let Ok(n) = s.parse::<i64>() {
+            if n.to_string() == s {
+                return Answer::Integer(n);
+            }
+        }
+        if let Ok(n) = s.parse::<BigInt>() {
+            if n.to_string() == s {
+                return Answer::BigInteger(n);
+            }
+        }
+        if s.contains('\n') {
+            return Answer::grid(s.lines());
+        }
+        Answer::Text(s.to_string())
+    }
+
+    /// The answer as stored in the answers and submissions files, integers are bare, text is
+    /// quoted and grids are lists of rows so every answer reads back as the same type
+    pub fn to_value(&self) -> Value {
+        match self {
+            Answer::Integer(_) | Answer::BigInteger(_) => Value::Bare(self.to_string()),
+            Answer::Text(text) => Value::Text(text.clone()),
+            Answer::Grid(rows) => Value::List(rows.clone()),
+        }
+    }
+
+    /// Reads back an answer stored with [`Answer::to_value`]
+    pub fn from_value(value: Value) -> Result<Answer, String> {
+        match value {
+            Value::Bare(n) => match n.parse::<BigInt>() {
+                Ok(n) => Ok(Answer::from(n)),
+                Err(_) => Err(format!(
+                    "'{n}' isn't an integer, text answers must be quoted"
+                )),
+            },
+            Value::Text(text) => Ok(Answer::Text(text)),
+            Value::List(rows) => Ok(Answer::Grid(rows)),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Answer {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Integer(n),
+                    Err(_) => Answer::BigInteger(BigInt::from(n)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(5199usize), Answer::Integer(5199));
+        assert_eq!(Answer::from(-3isize), Answer::Integer(-3));
+        let big = Answer::from(u64::MAX);
+        assert!(matches!(big, Answer::BigInteger(_)));
+        assert_eq!(big.to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(BigInt::from(42)), Answer::Integer(42));
+        assert_eq!(Answer::grid(["#.#", ".#."]).to_string(), "#.#\n.#.");
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = [
+            Answer::Integer(223472064194845),
+            Answer::from(u128::MAX),
+            Answer::from("6,0"),
+            Answer::from("007"),
+            Answer::grid(["#.#", ".#."]),
+        ];
+        for answer in &answers {
+            assert_eq!(Answer::parse(&answer.to_string()), *answer);
+        }
+        assert_ne!(Answer::parse("12"), Answer::from("12"));
+
+        let stored = [Answer::from("12"), Answer::grid(["#.#"]), Answer::from(-3)];
+        for answer in answers.into_iter().chain(stored) {
+            assert_eq!(Answer::from_value(answer.to_value()), Ok(answer));
+        }
+        assert!(Answer::from_value(Value::Bare("LGXK".to_string())).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::answer::Answer;
+use crate::sections;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// Default answers file name, kept next to the inputs it belongs to
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Recorded answers keyed by day and part, stored as one `[day_NN]` section per day:
+///
+/// ```toml
+/// [day_01]
+/// part_1 = 1151792
+/// part_2 = "LGXK"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(usize, usize), Answer>,
+}
+
+impl Answers {
+    /// Loads the answers at `path`, `None` if no answers have been recorded there yet
+    pub fn load(path: &Path) -> Result<Option<Answers>, String> {
+        sections::load(path, Answers::parse)
+    }
+
+    pub fn parse(contents: &str) -> Result<Answers, String> {
+        let mut answers = Answers::default();
+        for entry in sections::parse(contents)? {
+            let err = |msg: &str| format!("line {}: {msg}", entry.line);
+            let part = match entry.key.as_str() {
+                "part_1" => 1,
+                "part_2" => 2,
+                key => return Err(err(&format!("unknown key '{key}'"))),
+            };
+            let answer = Answer::from_value(entry.value).map_err(|e| err(&e))?;
+            answers.entries.insert((entry.day, part), answer);
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&Answer> {
+        self.entries.get(&(day, part))
+    }
+
+    pub fn expected(&self, day: usize) -> [Option<Answer>; 2] {
+        [self.get(day, 1).cloned(), self.get(day, 2).cloned()]
+    }
+
+    pub fn record(&mut self, day: usize, part: usize, answer: &Answer) {
+        self.entries.insert((day, part), answer.clone());
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    fn to_toml(&self) -> String {
+        sections::write(self.entries.iter().map(|((day, part), answer)| {
+            let key = if *part == 1 { "part_1" } else { "part_2" };
+            (*day, key, answer.to_value())
+        }))
+    }
+}
+
+/// The answers file for an input directory
+pub fn answers_path(dir: &Path) -> PathBuf {
+    dir.join(ANSWERS_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(10, 2, &Answer::from(1324));
+        answers.record(1, 1, &Answer::from(1151792));
+        answers.record(1, 2, &Answer::from("LGX\"K"));
+        answers.record(2, 1, &Answer::grid(["#..#", "####"]));
+        answers.record(2, 2, &Answer::from("12"));
+        answers.record(3, 1, &Answer::grid(["#..#"]));
+        let toml = answers.to_toml();
+        assert!(toml.starts_with("[day_01]\npart_1 = 1151792\npart_2 = \"LGX\\\"K\"\n"));
+        assert!(toml.contains("part_1 = [\"#..#\", \"####\"]\npart_2 = \"12\"\n"));
+        assert_eq!(Answers::parse(&toml).unwrap(), answers);
+        assert_eq!(answers.expected(10), [None, Some(Answer::Integer(1324))]);
+    }
+
+    #[test]
+    fn test_invalid_answers() {
+        assert!(Answers::parse("part_1 = \"3\"").is_err());
+        assert!(Answers::parse("[day_01]\npart_3 = \"3\"").is_err());
+        assert!(Answers::parse("[day_01]\npart_1 = LGXK").is_err());
+        assert!(Answers::parse("# Comment\n\n[day_01]\npart_1 = 3").is_ok());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::sections::{self, Value};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io};
+
+/// Default baseline file name, kept next to the inputs the timings were measured on
+pub const BASELINE_FILE: &str = "baseline.toml";
+
+const PHASE_KEYS: [&str; 3] = ["parse", "part_1", "part_2"];
+const PHASE_NAMES: [&str; 3] = ["Parse", "Part 1", "Part 2"];
+
+/// Timings of the parse, part 1 and part 2 phases of a day, `None` for phases that weren't run
+pub type PhaseTimings = [Option<Duration>; 3];
+
+/// Recorded phase timings in nanoseconds, one `[day_NN]` section per day:
+///
+/// ```toml
+/// [day_06]
+/// parse = 865300
+/// part_1 = 528200
+/// part_2 = 590413700
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    entries: BTreeMap<(usize, usize), Duration>,
+}
+
+impl Baseline {
+    /// Loads the baseline at `path`, `None` if no baseline has been saved there yet
+    pub fn load(path: &Path) -> Result<Option<Baseline>, String> {
+        sections::load(path, Baseline::parse)
+    }
+
+    pub fn parse(contents: &str) -> Result<Baseline, String> {
+        let mut baseline = Baseline::default();
+        for entry in sections::parse(contents)? {
+            let err = |msg: &str| format!("line {}: {msg}", entry.line);
+            let phase = PHASE_KEYS
+                .iter()
+                .position(|k| *k == entry.key)
+                .ok_or_else(|| err(&format!("unknown key '{}'", entry.key)))?;
+            let nanos = match entry.value {
+                Value::Bare(v) => v.parse().map_err(|_| err("expected nanoseconds"))?,
+                Value::Text(_) | Value::List(_) => return Err(err("expected nanoseconds")),
+            };
+            baseline
+                .entries
+                .insert((entry.day, phase), Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    pub fn timings(&self, day: usize) -> PhaseTimings {
+        [0, 1, 2].map(|phase| self.entries.get(&(day, phase)).copied())
+    }
+
+    pub fn record(&mut self, day: usize, timings: &PhaseTimings) {
+        for (phase, timing) in timings.iter().enumerate() {
+            if let Some(t) = timing {
+                self.entries.insert((day, phase), *t);
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    fn to_toml(&self) -> String {
+        sections::write(self.entries.iter().map(|((day, phase), timing)| {
+            (
+                *day,
+                PHASE_KEYS[*phase],
+                Value::Bare(timing.as_nanos().to_string()),
+            )
+        }))
+    }
+}
+
+/// The baseline file for an input directory
+pub fn baseline_path(dir: &Path) -> PathBuf {
+    dir.join(BASELINE_FILE)
+}
+
+/// Change of a single phase against its baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    pub phase: usize,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change in percent, positive when slower
+    pub change: f64,
+    pub regression: bool,
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<7}{:>11.1?} -> {:>11.1?} {:>+8.1}%",
+            PHASE_NAMES[self.phase], self.baseline, self.current, self.change
+        )
+    }
+}
+
+/// Compares the phases that ran against the baseline, flagging phases that got slower by more than
+/// `threshold` percent.
+pub fn compare(baseline: &PhaseTimings, current: &PhaseTimings, threshold: f64) -> Vec<Delta> {
+    (0..3)
+        .filter_map(|phase| match (baseline[phase], current[phase]) {
+            (Some(b), Some(c)) if !b.is_zero() => {
+                let change = (c.as_secs_f64() / b.as_secs_f64() - 1.0) * 100.0;
+                Some(Delta {
+                    phase,
+                    baseline: b,
+                    current: c,
+                    change,
+                    regression: change > threshold,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut baseline = Baseline::default();
+        let timings = [
+            Some(Duration::from_micros(865)),
+            None,
+            Some(Duration::from_millis(590)),
+        ];
+        baseline.record(6, &timings);
+        let toml = baseline.to_toml();
+        assert_eq!(toml, "[day_06]\nparse = 865000\npart_2 = 590000000\n");
+        assert_eq!(Baseline::parse(&toml).unwrap().timings(6), timings);
+        assert!(Baseline::parse("[day_06]\nparse = \"fast\"").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = [
+            Some(Duration::from_micros(100)),
+            Some(Duration::from_micros(100)),
+            None,
+        ];
+        let current = [
+            Some(Duration::from_micros(105)),
+            Some(Duration::from_micros(150)),
+            Some(Duration::from_micros(1)),
+        ];
+        let deltas = compare(&baseline, &current, 10.0);
+        assert_eq!(deltas.len(), 2);
+        assert!(!deltas[0].regression);
+        assert!(deltas[1].regression);
+        assert!((deltas[1].change - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::allocs::PhaseAllocs;
+use crate::cli::Parts;
+use crate::solution::Day;
+use crate::DayResult;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Summary statistics over the samples of a single phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        // Nearest-rank percentile
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95,
+        })
+    }
+}
+
+/// Statistics per phase, `None` for phases that weren't run
+#[derive(Debug)]
+pub struct BenchResult {
+    pub iterations: usize,
+    pub warmup: usize,
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+}
+
+impl Display for BenchResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bench : {} iterations", self.iterations)?;
+        if self.warmup > 0 {
+            write!(f, " ({} warm-up discarded)", self.warmup)?;
+        }
+        writeln!(
+            f,
+            "\n{:<7}{:>11}{:>11}{:>11}{:>11}{:>11}",
+            "", "min", "median", "mean", "stddev", "p95"
+        )?;
+        for (phase, stats) in [
+            ("Parse", self.parse),
+            ("Part 1", self.part_1),
+            ("Part 2", self.part_2),
+        ] {
+            if let Some(s) = stats {
+                writeln!(
+                    f,
+                    "{phase:<7}{:>11.1?}{:>11.1?}{:>11.1?}{:>11.1?}{:>11.1?}",
+                    s.min, s.median, s.mean, s.stddev, s.p95
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs every input of a day once, merging the results of inputs that only feed a single part.
+pub fn run_inputs(d: &Day, inputs: &[(String, Parts)]) -> DayResult {
+    let mut parse_duration: Option<Duration> = None;
+    let (mut p1, mut p2) = (None, None);
+    let mut allocs: PhaseAllocs = [None; 3];
+    for (contents, parts) in inputs {
+        let (parse, r1, r2, a) = (d.run)(contents, *parts);
+        if let Some(p) = parse {
+            parse_duration = Some(parse_duration.unwrap_or_default() + p);
+        }
+        p1 = p1.or(r1);
+        p2 = p2.or(r2);
+        for (total, a) in allocs.iter_mut().zip(a) {
+            *total = match (*total, a) {
+                (Some(t), Some(a)) => Some(t.merge(a)),
+                (t, a) => t.or(a),
+            };
+        }
+    }
+    (parse_duration, p1, p2, allocs)
+}
+
+/// Runs a day `warmup + iterations` times, discarding the warm-up runs from the statistics.
+pub fn bench(d: &Day, inputs: &[(String, Parts)], iterations: usize, warmup: usize) -> BenchResult {
+    let (mut parse, mut part_1, mut part_2) = (Vec::new(), Vec::new(), Vec::new());
+    for i in 0..warmup + iterations {
+        let (parse_duration, p1, p2, _) = run_inputs(d, inputs);
+        if i < warmup {
+            continue;
+        }
+        parse.extend(parse_duration);
+        part_1.extend(p1.map(|(_, d)| d));
+        part_2.extend(p2.map(|(_, d)| d));
+    }
+    BenchResult {
+        iterations,
+        warmup,
+        parse: Stats::from_samples(&parse),
+        part_1: Stats::from_samples(&part_1),
+        part_2: Stats::from_samples(&part_2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=20).map(Duration::from_micros).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(10_500));
+        assert_eq!(stats.mean, Duration::from_nanos(10_500));
+        assert_eq!(stats.p95, Duration::from_micros(19));
+        assert!(stats.stddev > Duration::from_micros(5) && stats.stddev < Duration::from_micros(6));
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_bench_discards_warmup() {
+        let d = &crate::solution::DAYS[0];
+        let input = vec![(
+            std::fs::read_to_string("./src/example/day_01.txt").expect("File not found."),
+            Parts::Only(1),
+        )];
+        let result = bench(d, &input, 5, 2);
+        assert_eq!(result.iterations, 5);
+        assert!(result.parse.is_some() && result.part_1.is_some());
+        assert!(result.part_2.is_none());
+    }
+}
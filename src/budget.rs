@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Share of the total budget a run may use before its runtime is shown as a warning
+const WARNING_SHARE: f64 = 0.8;
+
+/// Time budgets to hold the solutions to, one second for all days by default
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    pub total: Duration,
+    /// Budget of every day without a budget of its own
+    pub day: Option<Duration>,
+    pub days: BTreeMap<usize, Duration>,
+}
+
+impl Default for Budget {
+    fn default() -> Budget {
+        Budget {
+            total: Duration::from_secs(1),
+            day: None,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+/// How a runtime compares to its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    Within,
+    /// Within the budget, but close to using all of it
+    Warning,
+    Over,
+}
+
+impl Budget {
+    pub fn day_budget(&self, day: usize) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.day)
+    }
+
+    /// Parses a `--day-budget` value, either `<DURATION>` for every day or `<DAY>=<DURATION>`
+    pub fn add_day_budget(&mut self, value: &str) -> Result<(), String> {
+        match value.split_once('=') {
+            Some((day, budget)) => {
+                let day = day
+                    .parse()
+                    .map_err(|_| format!("Invalid day '{day}' in day budget '{value}'"))?;
+                self.days.insert(day, parse_duration(budget)?);
+            }
+            None => self.day = Some(parse_duration(value)?),
+        }
+        Ok(())
+    }
+
+    /// Part of the total budget taken up by `runtime`, in percent
+    pub fn share(&self, runtime: Duration) -> f64 {
+        runtime.as_secs_f64() / self.total.as_secs_f64() * 100.0
+    }
+
+    pub fn day_usage(&self, day: usize, runtime: Duration) -> Usage {
+        match self.day_budget(day) {
+            Some(budget) if runtime > budget => Usage::Over,
+            _ => Usage::Within,
+        }
+    }
+
+    pub fn total_usage(&self, runtime: Duration) -> Usage {
+        if runtime > self.total {
+            Usage::Over
+        } else if runtime.as_secs_f64() > self.total.as_secs_f64() * WARNING_SHARE {
+            Usage::Warning
+        } else {
+            Usage::Within
+        }
+    }
+}
+
+/// Parses durations like `1s`, `800ms`, `250us`, `250µs` or `40ns`, fractions are allowed
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let scale = match unit {
+        "s" => 1.0,
+        "ms" => 1e-3,
+        "us" | "µs" => 1e-6,
+        "ns" => 1e-9,
+        _ => return Err(format!("Invalid duration '{value}', expected e.g. 800ms")),
+    };
+    match amount.parse::<f64>() {
+        Ok(amount) if amount > 0.0 => Duration::try_from_secs_f64(amount * scale)
+            .map_err(|_| format!("Duration '{value}' is too long")),
+        _ => Err(format!("Invalid duration '{value}', expected e.g. 800ms")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1s"), Ok(Duration::from_secs(1)));
+        assert_eq!(parse_duration("1.5ms"), Ok(Duration::from_micros(1500)));
+        assert_eq!(parse_duration("250µs"), Ok(Duration::from_micros(250)));
+        assert!(parse_duration("800").is_err());
+        assert!(parse_duration("0ms").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("99999999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn test_usage() {
+        let mut budget = Budget::default();
+        budget.add_day_budget("50ms").unwrap();
+        budget.add_day_budget("6=600ms").unwrap();
+        assert!(budget.add_day_budget("six=600ms").is_err());
+        assert_eq!(
+            budget.day_usage(6, Duration::from_millis(590)),
+            Usage::Within
+        );
+        assert_eq!(budget.day_usage(7, Duration::from_millis(51)), Usage::Over);
+        assert_eq!(
+            budget.total_usage(Duration::from_millis(603)),
+            Usage::Within
+        );
+        assert_eq!(
+            budget.total_usage(Duration::from_millis(900)),
+            Usage::Warning
+        );
+        assert_eq!(budget.total_usage(Duration::from_millis(1001)), Usage::Over);
+        assert!((budget.share(Duration::from_millis(590)) - 59.0).abs() < 1e-9);
+    }
+}
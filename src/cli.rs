@@ -0,0 +1,586 @@
+use crate::budget::{self, Budget};
+use crate::input::STDIN;
+use crate::output::Format;
+use crate::runner::Schedule;
+use crate::theme::Theme;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: aoc [OPTIONS] [DAYS...]
+       aoc <COMMAND> <DAY> [ARGS...]
+
+Days may be given as single days (7), inclusive ranges (3..=8) or half-open ranges (3..8).
+Every implemented day is run when no days are given.
+
+Commands:
+  scaffold <DAY>          Generate the module, example file and registration of a new day
+  fetch <DAY>             Download the puzzle input of a day into the input directory, using the
+                          session token in $AOC_SESSION or .session
+  submit <DAY> <PART>     Solve a part and submit its answer, unless it's known to be wrong or the
+                          day is still cooling down from the last attempt
+  profile <DAY> <PART> [-n, --iterations <N>]
+                          Parse the input once and run a part N times in a tight loop, for
+                          profilers like perf (default: 100)
+
+fetch, submit and profile work on the input set picked by -i/--input-dir and --profile, the same
+one a run with these options reads.
+
+Options:
+  -a, --all               Run every implemented day
+  -p, --part <PART>       Only run part 1 or part 2 of each selected day
+  -i, --input-dir <DIR>   Read inputs from DIR (default: $AOC_INPUT_DIR, then src/input)
+      --profile <NAME>    Run against the input set in <DIR>/<NAME>, may be repeated
+      --all-profiles      Run against every input set found in the input directory
+  -e, --example           Run against the bundled example inputs and check the known answers
+      --input <PATH>      Run a single day against PATH, or stdin when PATH is '-'
+      --answers <PATH>    Check answers against PATH (default: answers.toml in the input directory)
+      --record            Record the current answers instead of checking them
+      --bench <N>         Run each day N times and report statistics per phase
+      --warmup <N>        Discard N extra warm-up runs before benchmarking (default: 0)
+      --baseline <PATH>   Compare timings against PATH (default: baseline.toml in the input directory)
+      --save-baseline     Save the current timings as the new baseline
+      --threshold <PCT>   Flag phases more than PCT percent slower than the baseline (default: 10)
+      --fail-on-regression  Fail the run when a phase is slower than the baseline
+      --budget <DURATION> Total time budget of all days, e.g. 800ms (default: 1s)
+      --day-budget <[DAY=]DURATION>  Time budget of every day, or of a single day, may be repeated
+  -w, --watch             Re-run a single day when its input changes, and its tests when its
+                          source or example changes
+      --timeout <DURATION>  Give up on a day that runs longer than DURATION, e.g. 10s
+  -j, --parallel          Run the selected days concurrently, each day is still timed on its own.
+                          Without it days run one at a time, for the most accurate timings. Not
+                          available with --bench
+  -f, --format <FORMAT>   Output format: pretty (default), plain, json, csv, or junit and tap with
+                          a test case per day and part
+      --readme            Regenerate the results section of README.md from a sequential run of
+                          both parts of every implemented day
+      --redact            Hide the answers in the README results
+      --theme <THEME>     Look of the day banners: festive (default) or plain, followed by any of
+                          colors=red:green, emoji=🎄, banner=underline|line and ascii=true, e.g.
+                          festive,colors=blue:cyan (default: $AOC_THEME, or plain ASCII when
+                          stdout isn't a terminal). No colors when $NO_COLOR is set
+  -v, --verbose           Log more details, may be repeated (default: warnings, or $RUST_LOG)
+  -h, --help              Print this help";
+
+/// Which parts of a day should be executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    Both,
+    Only(u8),
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Only(p) => p == part,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub days: Vec<usize>,
+    pub parts: Parts,
+    pub input_dir: Option<PathBuf>,
+    pub profiles: Vec<String>,
+    pub all_profiles: bool,
+    pub example: bool,
+    pub input: Option<PathBuf>,
+    pub answers: Option<PathBuf>,
+    pub record: bool,
+    pub bench: Option<usize>,
+    pub warmup: usize,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: bool,
+    pub threshold: f64,
+    pub fail_on_regression: bool,
+    pub budget: Budget,
+    pub timeout: Option<Duration>,
+    pub watch: bool,
+    pub schedule: Schedule,
+    pub format: Format,
+    pub readme: bool,
+    pub verbose: u8,
+    pub redact: bool,
+    pub theme: Option<Theme>,
+}
+
+/// The input set a subcommand works on, picked the same way as for a run
+#[derive(Debug, Default, PartialEq)]
+pub struct InputArgs {
+    pub input_dir: Option<PathBuf>,
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Box<Args>),
+    Scaffold(usize),
+    Fetch(usize, InputArgs),
+    Submit(usize, u8, InputArgs),
+    /// Runs a single part over and over on one parse of its input, for external profilers
+    Profile {
+        day: usize,
+        part: u8,
+        iterations: usize,
+        inputs: InputArgs,
+    },
+    Help,
+}
+
+/// Parses the command line (without the binary name) against the list of implemented days.
+/// Days are returned sorted and de-duplicated so each day runs once in calendar order.
+pub fn parse_args<I>(args: I, implemented: &[usize]) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut days = Vec::new();
+    let mut parts = Parts::Both;
+    let mut all = false;
+    let mut input_dir = None;
+    let mut profiles = Vec::new();
+    let mut all_profiles = false;
+    let mut example = false;
+    let mut input = None;
+    let mut answers = None;
+    let mut record = false;
+    let mut bench = None;
+    let mut warmup = 0;
+    let mut baseline = None;
+    let mut save_baseline = false;
+    let mut threshold = 10.0;
+    let mut fail_on_regression = false;
+    let mut budget = Budget::default();
+    let mut timeout = None;
+    let mut watch = false;
+    let mut schedule = Schedule::Sequential;
+    let mut format = Format::Pretty;
+    let mut readme = false;
+    let mut verbose = 0;
+    let mut redact = false;
+    let mut theme = None;
+    let mut args = args.into_iter().peekable();
+    if let Some(command) = args.next_if(|a| COMMANDS.contains(&a.as_str())) {
+        return parse_command(&command, args, implemented);
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--all" => all = true,
+            "-p" | "--part" => {
+                parts = Parts::Only(parse_part(&args.next().ok_or("--part requires a value")?)?);
+            }
+            "-i" | "--input-dir" => {
+                input_dir = Some(args.next().ok_or("--input-dir requires a value")?.into());
+            }
+            "--profile" => {
+                let value = args.next().ok_or("--profile requires a value")?;
+                profiles.extend(value.split(',').map(|p| p.to_string()));
+            }
+            "--all-profiles" => all_profiles = true,
+            "-e" | "--example" => example = true,
+            "--input" => input = Some(args.next().ok_or("--input requires a value")?.into()),
+            "--answers" => {
+                answers = Some(args.next().ok_or("--answers requires a value")?.into());
+            }
+            "--record" => record = true,
+            "--bench" => bench = Some(parse_count(args.next(), "--bench")?),
+            "--warmup" => warmup = parse_count(args.next(), "--warmup")?,
+            "--baseline" => {
+                baseline = Some(args.next().ok_or("--baseline requires a value")?.into());
+            }
+            "--save-baseline" => save_baseline = true,
+            "--threshold" => {
+                let value = args.next().ok_or("--threshold requires a value")?;
+                threshold = match value.parse::<f64>() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => {
+                        return Err(format!(
+                            "Invalid threshold '{value}', expected a percentage"
+                        ))
+                    }
+                };
+            }
+            "--fail-on-regression" => fail_on_regression = true,
+            "--budget" => {
+                budget.total =
+                    budget::parse_duration(&args.next().ok_or("--budget requires a value")?)?;
+            }
+            "--day-budget" => {
+                budget.add_day_budget(&args.next().ok_or("--day-budget requires a value")?)?;
+            }
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout requires a value")?;
+                timeout = Some(budget::parse_duration(&value)?);
+            }
+            "-w" | "--watch" => watch = true,
+            "-j" | "--parallel" => schedule = Schedule::Parallel,
+            "-f" | "--format" => {
+                format = args.next().ok_or("--format requires a value")?.parse()?
+            }
+            "-v" | "--verbose" => verbose += 1,
+            "-vv" => verbose += 2,
+            "--readme" => readme = true,
+            "--redact" => redact = true,
+            "--theme" => {
+                theme = Some(Theme::parse(
+                    &args.next().ok_or("--theme requires a value")?,
+                )?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'")),
+            _ => days.extend(parse_days(&arg)?),
+        }
+    }
+
+    if input.is_some() {
+        if days.len() != 1 || all {
+            return Err("--input requires exactly one day".to_string());
+        }
+        if example || all_profiles || !profiles.is_empty() {
+            return Err("--input can't be combined with --example or profiles".to_string());
+        }
+    }
+    if example && (record || answers.is_some()) {
+        return Err(
+            "Example answers are built in, --example can't use an answers file".to_string(),
+        );
+    }
+    if record && input.is_some() && answers.is_none() {
+        return Err("--record with --input needs an --answers file to record to".to_string());
+    }
+    if save_baseline && (example || input.is_some()) && baseline.is_none() {
+        return Err(
+            "--save-baseline with --example or --input needs a --baseline file to save to"
+                .to_string(),
+        );
+    }
+    if (answers.is_some() || baseline.is_some()) && (all_profiles || profiles.len() > 1) {
+        return Err(
+            "--answers and --baseline can only be used with a single input set".to_string(),
+        );
+    }
+    if readme && (example || input.is_some() || all_profiles || profiles.len() > 1) {
+        return Err("--readme only publishes a run over a single set of puzzle inputs".to_string());
+    }
+    if watch {
+        if days.len() != 1 || all {
+            return Err("--watch requires exactly one day".to_string());
+        }
+        if all_profiles || profiles.len() > 1 || input.as_deref() == Some(Path::new(STDIN)) {
+            return Err("--watch needs a single input set and can't watch stdin".to_string());
+        }
+        if record || save_baseline || readme {
+            return Err(
+                "--watch can't be combined with --record, --save-baseline or --readme".to_string(),
+            );
+        }
+    }
+    if redact && !readme {
+        return Err("--redact only applies to --readme".to_string());
+    }
+    if all || days.is_empty() {
+        days = implemented.to_vec();
+    }
+    days.sort_unstable();
+    days.dedup();
+    let missing = days
+        .iter()
+        .filter(|d| !implemented.contains(d))
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Day(s) {} not implemented yet, available days: {}",
+            missing.join(", "),
+            implemented
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    if bench.is_some() && schedule == Schedule::Parallel {
+        return Err(
+            "--bench runs days one at a time, concurrent days would skew the statistics"
+                .to_string(),
+        );
+    }
+    if readme && (days != implemented || parts != Parts::Both || schedule != Schedule::Sequential) {
+        return Err(
+            "--readme needs a sequential run of both parts of every implemented day".to_string(),
+        );
+    }
+    Ok(Command::Run(Box::new(Args {
+        days,
+        parts,
+        input_dir,
+        profiles,
+        all_profiles,
+        example,
+        input,
+        answers,
+        record,
+        bench,
+        warmup,
+        baseline,
+        save_baseline,
+        threshold,
+        fail_on_regression,
+        budget,
+        timeout,
+        watch,
+        schedule,
+        format,
+        readme,
+        verbose,
+        redact,
+        theme,
+    })))
+}
+
+fn parse_count(value: Option<String>, option: &str) -> Result<usize, String> {
+    let value = value.ok_or(format!("{option} requires a value"))?;
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("Invalid count '{value}' for {option}")),
+    }
+}
+
+/// Subcommands taking the place of the days to run
+const COMMANDS: [&str; 4] = ["scaffold", "fetch", "submit", "profile"];
+
+/// Iterations of the profiling loop when `--iterations` isn't given
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+/// Parses the arguments of a subcommand, only days that aren't implemented yet can be scaffolded
+/// and only implemented ones submitted or profiled
+fn parse_command<I>(command: &str, mut args: I, implemented: &[usize]) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let day = parse_day(&args.next().ok_or(format!("{command} requires a day"))?)?;
+    match command {
+        "scaffold" if implemented.contains(&day) => {
+            return Err(format!("Day {day} is already implemented"))
+        }
+        "scaffold" | "fetch" => {}
+        _ if !implemented.contains(&day) => {
+            return Err(format!("Day {day} is not implemented yet"))
+        }
+        _ => {}
+    }
+    let part = match command {
+        "submit" | "profile" => Some(parse_part(
+            &args.next().ok_or(format!("{command} requires a part"))?,
+        )?),
+        _ => None,
+    };
+
+    let mut inputs = InputArgs::default();
+    let mut iterations = DEFAULT_ITERATIONS;
+    while let Some(arg) = args.next() {
+        match (command, arg.as_str()) {
+            ("scaffold", _) => return Err(format!("Unexpected argument '{arg}' for {command}")),
+            (_, "-i" | "--input-dir") => {
+                inputs.input_dir = Some(args.next().ok_or("--input-dir requires a value")?.into());
+            }
+            (_, "--profile") => {
+                let value = args.next().ok_or("--profile requires a value")?;
+                if inputs.profile.is_some() || value.contains(',') {
+                    return Err(format!(
+                        "{command} works on the inputs of a single --profile"
+                    ));
+                }
+                inputs.profile = Some(value);
+            }
+            ("profile", "-n" | "--iterations") => {
+                iterations = parse_count(args.next(), "--iterations")?;
+            }
+            _ => return Err(format!("Unexpected argument '{arg}' for {command}")),
+        }
+    }
+    Ok(match (command, part) {
+        ("scaffold", _) => Command::Scaffold(day),
+        ("submit", Some(part)) => Command::Submit(day, part, inputs),
+        ("profile", Some(part)) => Command::Profile {
+            day,
+            part,
+            iterations,
+            inputs,
+        },
+        _ => Command::Fetch(day, inputs),
+    })
+}
+
+fn parse_part(arg: &str) -> Result<u8, String> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part '{arg}', expected 1 or 2")),
+    }
+}
+
+fn parse_day(arg: &str) -> Result<usize, String> {
+    match arg.parse() {
+        Ok(d @ 1..=25) => Ok(d),
+        _ => Err(format!(
+            "Invalid day '{arg}', expected a number from 1 to 25"
+        )),
+    }
+}
+
+fn parse_days(arg: &str) -> Result<RangeInclusive<usize>, String> {
+    let range = if let Some((start, end)) = arg.split_once("..=") {
+        parse_day(start)?..=parse_day(end)?
+    } else if let Some((start, end)) = arg.split_once("..") {
+        parse_day(start)?..=parse_day(end)?.saturating_sub(1)
+    } else {
+        let d = parse_day(arg)?;
+        d..=d
+    };
+    if range.is_empty() {
+        return Err(format!("Day range '{arg}' is empty"));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IMPLEMENTED: [usize; 4] = [1, 2, 3, 7];
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|a| a.to_string()), &IMPLEMENTED)
+    }
+
+    #[test]
+    fn test_day_selection() {
+        let Ok(Command::Run(args)) = parse(&["7", "1..=3", "2"]) else {
+            panic!("Expected days to parse")
+        };
+        assert_eq!(args.days, vec![1, 2, 3, 7]);
+        assert_eq!(args.parts, Parts::Both);
+        let Ok(Command::Run(args)) = parse(&["--part", "2", "1..3"]) else {
+            panic!("Expected days to parse")
+        };
+        assert_eq!(args.days, vec![1, 2]);
+        assert!(!args.parts.includes(1));
+        assert!(parse(&["--all"]).is_ok_and(|c| c == parse(&[]).unwrap()));
+        let Ok(Command::Run(args)) = parse(&["-i", "inputs", "--profile", "a,b", "--profile", "c"])
+        else {
+            panic!("Expected input options to parse")
+        };
+        assert_eq!(args.input_dir, Some(PathBuf::from("inputs")));
+        assert_eq!(args.profiles, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_invalid_selection() {
+        assert!(parse(&["4"]).unwrap_err().contains("not implemented"));
+        assert!(parse(&["26"]).is_err());
+        assert!(parse(&["3..=1"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_input_args() {
+        assert!(parse(&["--input", "-", "7"]).is_ok());
+        assert!(parse(&["--input", "-", "1..=2"]).is_err());
+        assert!(parse(&["--input", "-", "--example", "1"]).is_err());
+        assert!(parse(&["--example", "--record"]).is_err());
+        assert!(parse(&["--input", "-", "--record", "7"]).is_err());
+        assert!(parse(&["--input", "-", "--record", "--answers", "a.toml", "7"]).is_ok());
+        assert!(parse(&["--answers", "a.toml", "--all-profiles"]).is_err());
+    }
+
+    #[test]
+    fn test_bench_args() {
+        assert!(parse(&["--bench", "100", "--warmup", "10"]).is_ok());
+        assert!(parse(&["--bench", "0"]).is_err());
+        assert!(parse(&["--bench", "5", "-j"]).is_err());
+        assert!(parse(&["--threshold", "-5"]).is_err());
+        assert!(parse(&["--baseline", "b.toml", "--profile", "a,b"]).is_err());
+        assert!(parse(&["--example", "--save-baseline"]).is_err());
+        assert!(parse(&["--input", "-", "--save-baseline", "7"]).is_err());
+        assert!(parse(&["--example", "--save-baseline", "--baseline", "b.toml"]).is_ok());
+        assert!(parse(&["--budget", "800"]).is_err());
+        assert!(parse(&["--day-budget", "6=600ms", "--day-budget", "40ms"]).is_ok());
+        assert!(parse(&["--timeout", "forever"]).is_err());
+        assert!(parse(&["--isolated"]).is_err());
+        assert!(parse(&["-j"])
+            .is_ok_and(|c| matches!(c, Command::Run(args) if args.schedule == Schedule::Parallel)));
+    }
+
+    #[test]
+    fn test_output_args() {
+        assert!(parse(&["--format", "yaml"]).is_err());
+        assert!(parse(&["--readme", "--redact"]).is_ok());
+        assert!(parse(&["--readme", "--example"]).is_err());
+        assert!(parse(&["--readme", "1..=3"]).is_err());
+        assert!(parse(&["--readme", "--part", "1"]).is_err());
+        assert!(parse(&["--readme", "-j"]).is_err());
+        assert!(parse(&["--redact"]).is_err());
+        assert!(parse(&["--theme", "plain,banner=line"]).is_ok());
+        assert!(parse(&["--theme", "colors=plaid"]).is_err());
+    }
+
+    #[test]
+    fn test_watch_args() {
+        assert!(parse(&["--watch", "7"]).is_ok());
+        assert!(parse(&["--watch", "1..=2"]).is_err());
+        assert!(parse(&["--watch", "--input", "-", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_args() {
+        assert_eq!(parse(&["scaffold", "11"]), Ok(Command::Scaffold(11)));
+        assert!(parse(&["scaffold", "7"])
+            .unwrap_err()
+            .contains("already implemented"));
+        assert!(parse(&["scaffold", "11", "12"]).is_err());
+        assert!(parse(&["scaffold"]).is_err());
+        assert!(parse(&["scaffold", "11", "-i", "inputs"]).is_err());
+    }
+
+    #[test]
+    fn test_fetch_args() {
+        assert_eq!(
+            parse(&["fetch", "25"]),
+            Ok(Command::Fetch(25, InputArgs::default()))
+        );
+        assert!(parse(&["fetch", "1..=3"]).is_err());
+    }
+
+    #[test]
+    fn test_submit_args() {
+        let zoey = InputArgs {
+            input_dir: Some(PathBuf::from("inputs")),
+            profile: Some("zoey".to_string()),
+        };
+        assert_eq!(
+            parse(&["submit", "7", "2", "-i", "inputs", "--profile", "zoey"]),
+            Ok(Command::Submit(7, 2, zoey))
+        );
+        assert!(parse(&["submit", "7", "2", "--profile", "zoey,felix"]).is_err());
+        assert!(parse(&["submit", "7"]).is_err());
+        assert!(parse(&["submit", "4", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_profile_args() {
+        assert_eq!(
+            parse(&["profile", "7", "2", "--iterations", "500"]),
+            Ok(Command::Profile {
+                day: 7,
+                part: 2,
+                iterations: 500,
+                inputs: InputArgs::default(),
+            })
+        );
+        assert!(parse(&["profile", "7", "2", "--iterations", "0"]).is_err());
+        assert!(parse(&["profile", "7", "--iterations", "5"]).is_err());
+    }
+}
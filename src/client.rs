@@ -0,0 +1,251 @@
+use crate::answer::Answer;
+use crate::input::InputSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs};
+
+/// Year the puzzles are fetched from
+pub const YEAR: usize = 2024;
+
+/// Environment variable with the session token, takes precedence over the session file
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable pointing the client at another server, e.g. a local stand-in
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the client to the site, as its maintainers ask automated tools to do
+const USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The session token file, `.session` next to `Cargo.toml` and kept out of git
+pub fn session_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(".session")
+}
+
+/// Talks to the Advent of Code website on behalf of a logged in user
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+
+    /// Builds a client from `AOC_SESSION` or the session file, and `AOC_BASE_URL`
+    pub fn from_env() -> Result<Client, String> {
+        let session = session(env::var(SESSION_VAR).ok(), &session_path())?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &session))
+    }
+
+    /// Downloads the puzzle input of a day
+    pub fn input(&self, day: usize) -> Result<String, String> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        body(day, &url, response)
+    }
+
+    /// Posts the answer to a part, returning the page telling whether it was right
+    pub fn submit(&self, day: usize, part: u8, answer: &Answer) -> Result<String, String> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ]);
+        body(day, &url, response)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Reads the body of a successful response, explaining the statuses the site answers with
+fn body(
+    day: usize,
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| format!("Could not read the response from {url}: {e}")),
+        Err(ureq::Error::Status(404, _)) => Err(format!("Day {day} of {YEAR} isn't unlocked yet")),
+        Err(ureq::Error::Status(400 | 401 | 403 | 500, _)) => Err(format!(
+            "The session token was rejected, update ${SESSION_VAR} or {}",
+            session_path().display()
+        )),
+        Err(ureq::Error::Status(status, _)) => {
+            Err(format!("Request to {url} failed with status {status}"))
+        }
+        Err(e) => Err(format!("Could not reach {url}: {e}")),
+    }
+}
+
+/// Picks the session token from the environment, falling back to the first line of `file`
+pub fn session(env_session: Option<String>, file: &Path) -> Result<String, String> {
+    let session = match env_session {
+        Some(session) => session,
+        None => match fs::read_to_string(file) {
+            Ok(contents) => contents.lines().next().unwrap_or_default().to_string(),
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Could not read {}: {e}", file.display())),
+        },
+    };
+    let session = session.trim().trim_start_matches("session=");
+    if session.is_empty() {
+        return Err(format!(
+            "No session token, set ${SESSION_VAR} or save the session cookie to {}",
+            file.display()
+        ));
+    }
+    Ok(session.to_string())
+}
+
+/// Outcome of fetching an input
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was already there, inputs never change so it isn't downloaded again
+    Cached(PathBuf),
+}
+
+/// Downloads the input of a day into the input set where the runner expects it, unless it's there
+/// already. The client is only built when a download is needed, so cached inputs work without a
+/// session.
+pub fn fetch(
+    day: usize,
+    set: &InputSet,
+    client: impl FnOnce() -> Result<Client, String>,
+) -> Result<Fetched, String> {
+    let path = set.path(day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client()?.input(day)?;
+    let dir = &set.dir;
+    fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    // Written next to its final path first so an interrupted download never looks cached
+    let partial = path.with_extension("part");
+    fs::write(&partial, input)
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Stands in for the website, answering a single request and handing back what it received
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_fetch() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let set = InputSet {
+            profile: Some("zoey".to_string()),
+            dir: dir.join("zoey"),
+        };
+        let (base_url, server) = serve_once("200 OK", "3   4\n4   3\n");
+        let fetched = fetch(1, &set, || Ok(Client::new(&base_url, "53cr37"))).unwrap();
+        let path = dir.join("zoey/day_01.txt");
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=53cr37\r\n"));
+        // Never asks the server again once the input is cached
+        let cached = fetch(1, &set, || Err("no client".to_string())).unwrap();
+        assert_eq!(cached, Fetched::Cached(path));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-errors-{}", std::process::id()));
+        let set = InputSet {
+            profile: None,
+            dir: dir.clone(),
+        };
+        let (base_url, server) = serve_once("404 Not Found", "Not found");
+        let error = fetch(25, &set, || Ok(Client::new(&base_url, "53cr37"))).unwrap_err();
+        assert!(error.contains("isn't unlocked"));
+        server.join().unwrap();
+        assert!(!dir.join("day_25.txt").exists());
+        assert_eq!(
+            session(Some(" session=53cr37\n".into()), &dir).unwrap(),
+            "53cr37"
+        );
+        assert!(session(None, &dir.join(".session")).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<article><p>That's the right answer!</p></article>",
+        );
+        let client = Client::new(&format!("{base_url}/"), "53cr37");
+        let response = client.submit(6, 2, &Answer::from("1,2 3")).unwrap();
+        assert!(response.contains("right answer"));
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/6/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=1%2C2+3"));
+    }
+}
@@ -1,7 +1,9 @@
+//! Day 1: Historian Hysteria
+
 extern crate core;
 
-use crate::DayResult;
-use std::time::Instant;
+use crate::answer::Answer;
+use crate::solution::Solution;
 
 use fxhash::FxHashMap;
 use num::abs;
@@ -11,22 +13,30 @@ const ASCII_SPACE: u8 = 32;
 const ASCII_0: u8 = 48;
 const ASCII_9: u8 = 57;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let (mut a, mut b) = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<isize>, Vec<isize>);
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("11"), Some("31")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&mut a, &mut b).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        let (a, b) = input;
+        part_1(a, b)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&mut a, &mut b).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        let (a, b) = input;
+        part_2(a, b)
+    }
 }
 
-fn parse(input: &str) -> (Vec<isize>, Vec<isize>) {
+/// Parses the two columns of location IDs into the left and right list
+pub fn parse(input: &str) -> (Vec<isize>, Vec<isize>) {
     let mut a = Vec::with_capacity(1500);
     let mut b = Vec::with_capacity(1500);
     let mut second_block = false;
@@ -55,6 +65,7 @@ fn parse(input: &str) -> (Vec<isize>, Vec<isize>) {
     (a, b)
 }
 
+/// Total distance between the lists, pairing up their smallest IDs first
 pub fn part_1(a: &mut [isize], b: &mut [isize]) -> isize {
     a.sort();
     b.sort();
@@ -65,6 +76,7 @@ pub fn part_1(a: &mut [isize], b: &mut [isize]) -> isize {
     sum
 }
 
+/// Similarity score, every left ID multiplied by how often it appears in the right list
 pub fn part_2(a: &mut [isize], b: &mut [isize]) -> isize {
     let mut frequencies: FxHashMap<isize, isize> = FxHashMap::default();
     for b in b.iter() {
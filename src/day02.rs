@@ -1,25 +1,32 @@
+//! Day 2: Red-Nosed Reports
+
 extern crate core;
 
 use crate::day02::LevelDirection::{Decreasing, Increasing, Unchanged};
 use crate::day02::ReactorHealth::{Safe, Unsafe};
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use num::abs;
 use std::cmp::{Ordering, PartialEq};
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let reactors = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Reactor>;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("2"), Some("4")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&mut reactors.clone()).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&mut reactors.clone()).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
 const ASCII_LINESEP: u8 = 10;
@@ -41,7 +48,7 @@ enum ReactorHealth {
 }
 
 #[derive(Clone, PartialEq, Debug)]
-struct Reactor {
+pub struct Reactor {
     // We use a fixed-length array to reduce Vec<isize> allocations, much faster! 600us -> 23us
     values: [isize; 10],
     health: ReactorHealth,
@@ -58,7 +65,8 @@ impl Default for Reactor {
     }
 }
 
-fn parse(input: &str) -> Vec<Reactor> {
+/// Parses every line into a report of levels
+pub fn parse(input: &str) -> Vec<Reactor> {
     let mut reactors: Vec<Reactor> = Vec::with_capacity(1000);
     let mut reactor = Reactor::default();
     let mut x = 0;
@@ -95,7 +103,8 @@ fn parse(input: &str) -> Vec<Reactor> {
     reactors
 }
 
-fn part_1(reactors: &mut Vec<Reactor>) -> usize {
+/// Number of safe reports
+pub fn part_1(reactors: &mut Vec<Reactor>) -> usize {
     let mut safe_count = 0;
     for reactor in reactors {
         run_reactor_test(reactor);
@@ -142,7 +151,8 @@ fn run_reactor_test(reactor: &mut Reactor) {
     }
 }
 
-fn part_2(reactors: &mut Vec<Reactor>) -> usize {
+/// Number of reports that are safe when the Problem Dampener may drop a single level
+pub fn part_2(reactors: &mut Vec<Reactor>) -> usize {
     let mut safe_count = 0;
 
     for reactor in reactors {
@@ -1,23 +1,35 @@
+//! Day 3: Mull It Over
+
 extern crate core;
 
 use crate::day03::Instruction::Mul;
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use logos::{Lexer, Logos};
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let instructions = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Instruction>;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("161"), Some("48")];
+    // Part 2 introduces do() and don't() so it comes with its own example
+    const EXAMPLE_INPUTS: [Option<&'static str>; 2] = [
+        None,
+        Some("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"),
+    ];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&instructions).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&instructions).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
 fn decode_mul(lex: &mut Lexer<Instruction>) -> Option<(usize, usize)> {
@@ -31,8 +43,8 @@ fn decode_mul(lex: &mut Lexer<Instruction>) -> Option<(usize, usize)> {
     Some((nums[0], nums[1]))
 }
 
-#[derive(Debug, Logos, PartialEq)]
-enum Instruction {
+#[derive(Debug, Clone, Logos, PartialEq)]
+pub enum Instruction {
     #[regex(r"mul\([0-9]{1,3},[0-9]{1,3}\)", decode_mul)]
     Mul((usize, usize)),
     #[token("do()")]
@@ -41,13 +53,15 @@ enum Instruction {
     Dont,
 }
 
-fn parse(input: &str) -> Vec<Instruction> {
+/// Lexes the corrupted memory into its instructions
+pub fn parse(input: &str) -> Vec<Instruction> {
     Instruction::lexer(input)
         .filter_map(|x| x.ok())
         .collect::<Vec<_>>()
 }
 
-fn part_1(instructions: &Vec<Instruction>) -> usize {
+/// Sum of all `mul` instructions
+pub fn part_1(instructions: &Vec<Instruction>) -> usize {
     let mut sum = 0;
     for i in instructions {
         if let Mul((a, b)) = i {
@@ -57,7 +71,8 @@ fn part_1(instructions: &Vec<Instruction>) -> usize {
     sum
 }
 
-fn part_2(instructions: &Vec<Instruction>) -> usize {
+/// Sum of the `mul` instructions enabled by `do()` and `don't()`
+pub fn part_2(instructions: &Vec<Instruction>) -> usize {
     let mut sum = 0;
     let mut enabled = true;
     for i in instructions {
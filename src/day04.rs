@@ -1,21 +1,28 @@
+//! Day 4: Ceres Search
+
 extern crate core;
 
-use crate::DayResult;
-use std::time::Instant;
+use crate::answer::Answer;
+use crate::solution::Solution;
+
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Puzzle;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("18"), Some("9")];
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let puzzle = parse(input);
-    let parse_duration = start.elapsed();
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&puzzle).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&puzzle).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
 // Just for sanity's sake and debugging ease we redefine the ASCII
@@ -25,8 +32,8 @@ const A: u32 = 3;
 const S: u32 = 4;
 const TOTAL_LETTERS: usize = 140 * 140; // From large puzzle input
 
-#[derive(Debug)]
-struct Puzzle {
+#[derive(Debug, Clone)]
+pub struct Puzzle {
     letters: [u32; TOTAL_LETTERS],
     width: usize,
 }
@@ -40,7 +47,8 @@ impl Default for Puzzle {
     }
 }
 
-fn parse(input: &str) -> Puzzle {
+/// Parses the word search into a grid of letters
+pub fn parse(input: &str) -> Puzzle {
     let mut puzzle = Puzzle::default();
     let mut width_check: usize = 0;
     let mut offset: usize = 0;
@@ -68,7 +76,8 @@ fn is_match(a: u32, b: u32, c: u32, d: u32) -> bool {
     ((a == X) & (b == M) & (c == A) & (d == S)) | ((a == S) & (b == A) & (c == M) & (d == X))
 }
 
-fn part_1(p: &Puzzle) -> usize {
+/// Number of times XMAS appears in any direction
+pub fn part_1(p: &Puzzle) -> usize {
     let mut found = 0;
     for i in 0..TOTAL_LETTERS {
         // Ensure we don't read across lines using modulo and horizontal scan backwards
@@ -134,7 +143,8 @@ fn is_cross_match(a: u32, b: u32, c: u32, d: u32, e: u32) -> bool {
         | ((a == S) & (b == M) & (c == A) & (d == M) & (e == S))
 }
 
-fn part_2(p: &Puzzle) -> usize {
+/// Number of MAS crosses
+pub fn part_2(p: &Puzzle) -> usize {
     let mut found = 0;
     // Don't bother scanning until we're on the 3rd line
     for i in p.width * 2..TOTAL_LETTERS {
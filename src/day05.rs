@@ -1,36 +1,40 @@
+//! Day 5: Print Queue
+
 extern crate core;
 
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use fxhash::FxHashMap;
 use itertools::Itertools;
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let data = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Puzzle;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("143"), Some("123")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let (p1, invalid_pages) = process_pages(&data);
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&data, invalid_pages).to_string();
-    let p2_duration = start.elapsed();
-    (
-        Some(parse_duration),
-        (p1.to_string(), p1_duration),
-        (p2, p2_duration),
-    )
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
-#[derive(Debug, Default)]
-struct Puzzle {
+#[derive(Debug, Default, Clone)]
+pub struct Puzzle {
     rules: FxHashMap<usize, Vec<usize>>,
     updates: Vec<Vec<usize>>,
 }
 
-fn parse(input: &str) -> Puzzle {
+/// Parses the page ordering rules and the updates
+pub fn parse(input: &str) -> Puzzle {
     let mut puzzle = Puzzle::default();
     let mut second_block = false;
     let mut update_offset = 0;
@@ -59,38 +63,37 @@ fn parse(input: &str) -> Puzzle {
     puzzle
 }
 
-fn process_pages(p: &Puzzle) -> (usize, Vec<Vec<usize>>) {
-    let mut invalid_updates = Vec::new();
-    let total = p
-        .updates
-        .iter()
-        .map(|u| {
-            let mut valid_update = true;
-            for (page_index, page) in u.iter().enumerate() {
-                let pages_after = &u[page_index..u.len()];
-                for pa in pages_after {
-                    if valid_update {
-                        if let Some(rule) = p.rules.get(page) {
-                            valid_update = rule.contains(pa);
-                        }
-                        if let Some(rule) = p.rules.get(pa) {
-                            valid_update = !rule.contains(page);
-                        }
-                    }
-                }
-            }
+/// Whether no page of the update comes after a page the rules put it before
+fn is_valid(rules: &FxHashMap<usize, Vec<usize>>, u: &[usize]) -> bool {
+    let mut valid_update = true;
+    for (page_index, page) in u.iter().enumerate() {
+        let pages_after = &u[page_index..u.len()];
+        for pa in pages_after {
             if valid_update {
-                u[(u.len() - 1) / 2]
-            } else {
-                invalid_updates.push(u.clone());
-                0
+                if let Some(rule) = rules.get(page) {
+                    valid_update = rule.contains(pa);
+                }
+                if let Some(rule) = rules.get(pa) {
+                    valid_update = !rule.contains(page);
+                }
             }
-        })
-        .sum();
-    (total, invalid_updates)
+        }
+    }
+    valid_update
+}
+
+/// Sum of the middle pages of the correctly ordered updates
+pub fn part_1(p: &Puzzle) -> usize {
+    p.updates
+        .iter()
+        .filter(|u| is_valid(&p.rules, u))
+        .map(|u| u[(u.len() - 1) / 2])
+        .sum()
 }
 
-fn part_2(p: &Puzzle, invalid_pages: Vec<Vec<usize>>) -> usize {
+/// Unfinished, always 0. Meant to sum the middle pages of the invalid updates once they've been
+/// put in order.
+pub fn part_2(p: &Puzzle) -> usize {
     0
 }
 
@@ -109,7 +112,6 @@ mod tests {
     fn test_part_two() {
         let input = fs::read_to_string("./src/example/day_05.txt").expect("File not found.");
         let x = parse(&input);
-        let (_, invalid_pages) = process_pages(&x);
-        assert_eq!(part_2(&x, invalid_pages), 9);
+        assert_eq!(part_2(&x), 9);
     }
 }
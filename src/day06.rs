@@ -1,34 +1,42 @@
+//! Day 6: Guard Gallivant
+
 extern crate core;
 
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use fxhash::{FxBuildHasher, FxHashMap, FxHashSet};
 use num::Complex;
 use std::collections::HashSet;
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let data = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Puzzle;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("41"), Some("6")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&mut data.clone()).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&mut data.clone()).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 type WalkResult = Option<HashSet<(Complex<isize>, Complex<isize>), FxBuildHasher>>;
 
 #[derive(Debug, Default, Clone)]
-struct Puzzle {
+pub struct Puzzle {
     map: FxHashMap<Complex<isize>, char>,
     cursor: Complex<isize>,
 }
 
-fn parse(input: &str) -> Puzzle {
+/// Parses the lab map and the guard's starting position
+pub fn parse(input: &str) -> Puzzle {
     let mut puzzle = Puzzle::default();
     for (y, line) in input.lines().enumerate() {
         for (x, char) in line.chars().enumerate() {
@@ -74,11 +82,13 @@ fn walk_guard(p: &mut Puzzle, check_loop: bool) -> WalkResult {
     Some(seen_positions)
 }
 
-fn part_1(p: &mut Puzzle) -> usize {
+/// Number of distinct positions the guard visits before leaving the map
+pub fn part_1(p: &mut Puzzle) -> usize {
     walk_guard(p, false).unwrap().len()
 }
 
-fn part_2(p: &mut Puzzle) -> usize {
+/// Number of positions a single obstruction would trap the guard in a loop from
+pub fn part_2(p: &mut Puzzle) -> usize {
     let starting_point = p.cursor;
     let visited = walk_guard(p, false).unwrap();
     // Walk with obstructions added
@@ -1,32 +1,40 @@
+//! Day 7: Bridge Repair
+
 extern crate core;
 
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use rayon::prelude::*;
 use std::collections::VecDeque;
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let data = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Puzzle;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("3749"), Some("11387")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&data).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&data).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 type Equation = (usize, Vec<usize>);
 
 #[derive(Debug, Default, Clone)]
-struct Puzzle {
+pub struct Puzzle {
     equations: Vec<Equation>,
 }
 
-fn parse(input: &str) -> Puzzle {
+/// Parses the calibration equations into their test value and numbers
+pub fn parse(input: &str) -> Puzzle {
     let mut puzzle = Puzzle::default();
     for line in input.lines() {
         let mut fields = line.split(": ");
@@ -102,11 +110,13 @@ fn run_dfs(equation: &Equation, concat: bool) -> usize {
     0
 }
 
-fn part_1(p: &Puzzle) -> usize {
+/// Total calibration result of the equations `+` and `*` can make true
+pub fn part_1(p: &Puzzle) -> usize {
     p.equations.par_iter().map(|e| run_dfs(e, false)).sum()
 }
 
-fn part_2(p: &Puzzle) -> usize {
+/// Total calibration result once concatenation is allowed as well
+pub fn part_2(p: &Puzzle) -> usize {
     p.equations.par_iter().map(|e| run_dfs(e, true)).sum()
 }
 
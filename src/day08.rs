@@ -1,31 +1,39 @@
+//! Day 8: Resonant Collinearity
+
 extern crate core;
 
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use fxhash::FxHashMap;
 use itertools::Itertools;
 use num::Complex;
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let data = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Puzzle;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("14"), Some("34")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&data).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&data).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 #[derive(Debug, Default, Clone)]
-struct Puzzle {
+pub struct Puzzle {
     positions: FxHashMap<Complex<isize>, char>,
 }
 
-fn parse(input: &str) -> Puzzle {
+/// Parses the positions of the antennas grouped by frequency
+pub fn parse(input: &str) -> Puzzle {
     let mut puzzle = Puzzle::default();
     for (y, line) in input.lines().enumerate() {
         for (x, char) in line.chars().enumerate() {
@@ -69,11 +77,13 @@ fn build_antinodes(antennas: &Puzzle, account_for_harmonics: bool) -> Puzzle {
     antinodes
 }
 
-fn part_1(p: &Puzzle) -> usize {
+/// Number of unique locations containing an antinode
+pub fn part_1(p: &Puzzle) -> usize {
     build_antinodes(p, false).positions.len()
 }
 
-fn part_2(p: &Puzzle) -> usize {
+/// Number of unique locations containing an antinode once resonant harmonics are accounted for
+pub fn part_2(p: &Puzzle) -> usize {
     build_antinodes(p, true).positions.len()
 }
 
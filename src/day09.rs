@@ -1,25 +1,34 @@
+//! Day 9: Disk Fragmenter
+
 extern crate core;
 
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use fxhash::{FxHashMap, FxHashSet};
 use itertools::Itertools;
+use log::Level::Debug;
+use log::{debug, log_enabled};
 use std::collections::VecDeque;
 use std::thread::current;
-use std::time::Instant;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let mut data = parse(input);
-    let parse_duration = start.elapsed();
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Disk;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("1928"), Some("2858")];
 
-    let start = Instant::now();
-    let p1 = part_1(&mut data.clone()).to_string();
-    let p1_duration = start.elapsed();
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
+
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&mut data.clone()).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -28,7 +37,7 @@ struct File {
 }
 
 #[derive(Debug, Clone)]
-struct Disk {
+pub struct Disk {
     blocks: Vec<Option<File>>,
     file_sizes: FxHashMap<usize, usize>,
 }
@@ -42,7 +51,8 @@ impl Default for Disk {
     }
 }
 
-fn parse(input: &str) -> Disk {
+/// Parses the disk map into its files and free space
+pub fn parse(input: &str) -> Disk {
     let mut disk = Disk::default();
     let diskmap: Vec<usize> = input
         .chars()
@@ -117,22 +127,23 @@ fn defrag(d: &mut Disk) {
     for (i) in (0..d.blocks.len()).rev() {
         if let Some(f) = &d.blocks[i] {
             if f.id != current_file_id {
-                println!("Testing File: {}, {:?}", f.id, free_spaces.iter().last());
+                debug!("Testing File: {}, {:?}", f.id, free_spaces.iter().last());
                 current_file_id = f.id;
                 if let Some((space_idx, space_len)) = free_spaces.iter().last() {
                     if space_len >= d.file_sizes.get(&f.id).unwrap() {
-                        println! {"Can move File ID: {} to Space Idx {}", f.id, space_idx}
+                        debug!("Can move File ID: {} to Space Idx {}", f.id, space_idx);
                     }
                 }
             }
         }
     }
 
-    println!("{:?}", d.file_sizes);
+    debug!("{:?}", d.file_sizes);
     fragment(d);
 }
 
-fn part_1(d: &mut Disk) -> usize {
+/// Filesystem checksum after moving single blocks to the leftmost free space
+pub fn part_1(d: &mut Disk) -> usize {
     fragment(d);
     d.blocks
         .iter()
@@ -144,17 +155,22 @@ fn part_1(d: &mut Disk) -> usize {
         .sum()
 }
 
-fn part_2(d: &mut Disk) -> usize {
+/// Unfinished, always 0. Meant to be the filesystem checksum after moving whole files to the
+/// leftmost free space that fits them.
+pub fn part_2(d: &mut Disk) -> usize {
     defrag(d);
 
-    /*d.blocks.iter().for_each(|b| {
-        match b {
-            Some(f) => print!("{}", f.id),
-            None => print!("."),
-        }
-    });
-
-     */
+    if log_enabled!(Debug) {
+        let blocks: String = d
+            .blocks
+            .iter()
+            .map(|b| match b {
+                Some(f) => f.id.to_string(),
+                None => ".".to_string(),
+            })
+            .collect();
+        debug!("{blocks}");
+    }
     0
 }
 
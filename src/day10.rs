@@ -1,36 +1,46 @@
+//! Day 10: Hoof It
+
 extern crate core;
 
 use std::collections::VecDeque;
 use std::ops::Div;
-use crate::DayResult;
+use crate::answer::Answer;
+use crate::solution::Solution;
 use fxhash::{FxHashMap, FxHashSet};
 use rayon::prelude::*;
-use std::time::Instant;
 use image::{Rgb, RgbImage};
 use itertools::Itertools;
+use log::Level::Debug;
+use log::{debug, log_enabled};
+
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day10;
 
-pub fn run(input: &str) -> DayResult {
-    let start = Instant::now();
-    let data = parse(input);
-    let parse_duration = start.elapsed();
+impl Solution for Day10 {
+    type Input = TopoMap;
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [Some("36"), Some("81")];
+
+    fn parse(input: &str) -> Self::Input {
+        parse(input)
+    }
 
-    let start = Instant::now();
-    let p1 = part_1(&data).to_string();
-    let p1_duration = start.elapsed();
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {
+        part_1(input)
+    }
 
-    let start = Instant::now();
-    let p2 = part_2(&data).to_string();
-    let p2_duration = start.elapsed();
-    (Some(parse_duration), (p1, p1_duration), (p2, p2_duration))
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {
+        part_2(input)
+    }
 }
 
-#[derive(Debug, Default)]
-struct TopoMap {
+#[derive(Debug, Default, Clone)]
+pub struct TopoMap {
     topo: FxHashMap<(isize, isize), u8>,
     trailheads: Vec<(isize, isize)>,
 }
 
-fn parse(input: &str) -> TopoMap {
+/// Parses the topographic map
+pub fn parse(input: &str) -> TopoMap {
     let mut topo_map = TopoMap::default();
     for (y, line) in input.lines().enumerate() {
         for (x, b) in line.bytes().enumerate() {
@@ -95,7 +105,7 @@ fn draw_viz(t: &TopoMap, visited_nodes: &FxHashSet<(isize, isize)>, iteration: u
 
 fn bfs(t: &TopoMap, count_unique_trails: bool) -> usize {
     //let mut iteration = 0;
-    t.trailheads
+    let scores = t.trailheads
         .par_iter()
         .map(|trailhead| {
             let mut visited = FxHashSet::default();
@@ -106,7 +116,6 @@ fn bfs(t: &TopoMap, count_unique_trails: bool) -> usize {
             while let Some(position) = queue.pop_front() {
                 // draw_viz(t, &visited, iteration);
                 //iteration += 1;
-                //println!("{:?}::: {:?} :: {:?} :: {:?}", score, t.topo.get(&position), position, visited);
                 if ! count_unique_trails {
                     if visited.contains(&position) {
                         continue;
@@ -136,15 +145,25 @@ fn bfs(t: &TopoMap, count_unique_trails: bool) -> usize {
                 }
             }
             score
-        })
-        .sum()
+        });
+    if log_enabled!(Debug) {
+        // Logged from the day's own thread, the rayon workers aren't tagged with the day and phase
+        let scores: Vec<usize> = scores.collect();
+        for (trailhead, score) in t.trailheads.iter().zip(&scores) {
+            debug!("Trailhead {trailhead:?} scores {score}");
+        }
+        return scores.iter().sum();
+    }
+    scores.sum()
 }
 
-fn part_1(t: &TopoMap) -> usize {
+/// Sum of the scores of all trailheads, the number of peaks each can reach
+pub fn part_1(t: &TopoMap) -> usize {
     bfs(t, false)
 }
 
-fn part_2(t: &TopoMap) -> usize {
+/// Sum of the ratings of all trailheads, the number of distinct trails each starts
+pub fn part_2(t: &TopoMap) -> usize {
     bfs(t, true)
 }
 
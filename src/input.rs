@@ -0,0 +1,142 @@
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No input file exists at the expected path, the day is skipped rather than failed
+    Missing(PathBuf),
+    /// The input exists but could not be read
+    Unreadable(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(f, "no input at {}", path.display()),
+            InputError::Unreadable(path, e) => {
+                write!(f, "could not read {}: {e}", path.display())
+            }
+        }
+    }
+}
+
+/// Path given to `--input` to read the puzzle input from stdin
+pub const STDIN: &str = "-";
+
+/// Environment variable used as the input directory when `--input-dir` isn't given
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// A directory of puzzle inputs, named after its profile when it belongs to one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputSet {
+    pub profile: Option<String>,
+    pub dir: PathBuf,
+}
+
+impl InputSet {
+    pub fn path(&self, day: usize) -> PathBuf {
+        self.dir.join(file_name(day))
+    }
+}
+
+pub fn file_name(day: usize) -> String {
+    format!("day_{:0>2}.txt", day)
+}
+
+/// Picks the input directory from the command line, then the environment, falling back to the
+/// crate's own `src/input` so the binary works from any working directory.
+pub fn input_dir(arg: Option<PathBuf>, env_dir: Option<PathBuf>) -> PathBuf {
+    arg.or(env_dir)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src/input"))
+}
+
+/// The bundled example inputs, `src/example/day_NN.txt`
+pub fn example_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/example")
+}
+
+/// Resolves the input sets to run. Without profiles the input directory itself is the only set,
+/// otherwise every profile is a sub-directory, e.g. `<dir>/<profile>/day_NN.txt`.
+pub fn input_sets(
+    dir: &Path,
+    profiles: &[String],
+    all_profiles: bool,
+) -> Result<Vec<InputSet>, String> {
+    let mut profiles = profiles.to_vec();
+    if all_profiles {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Could not list profiles in {}: {e}", dir.display()))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.path().is_dir() {
+                profiles.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        if profiles.is_empty() {
+            return Err(format!("No profiles found in {}", dir.display()));
+        }
+    }
+    if profiles.is_empty() {
+        return Ok(vec![InputSet {
+            profile: None,
+            dir: dir.to_path_buf(),
+        }]);
+    }
+    profiles.sort();
+    profiles.dedup();
+    Ok(profiles
+        .into_iter()
+        .map(|profile| InputSet {
+            dir: dir.join(&profile),
+            profile: Some(profile),
+        })
+        .collect())
+}
+
+pub fn read_input(path: &Path) -> Result<String, InputError> {
+    if path == Path::new(STDIN) {
+        let mut contents = String::new();
+        return match io::stdin().read_to_string(&mut contents) {
+            Ok(_) => Ok(contents),
+            Err(e) => Err(InputError::Unreadable(path.to_path_buf(), e)),
+        };
+    }
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => InputError::Missing(path.to_path_buf()),
+        _ => InputError::Unreadable(path.to_path_buf(), e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_input() {
+        assert!(read_input(Path::new("./src/example/day_01.txt")).is_ok());
+        let missing = read_input(Path::new("./src/example/day_00.txt"));
+        assert!(matches!(missing, Err(InputError::Missing(_))));
+        // Directories exist but can never be read as text
+        let unreadable = read_input(Path::new("./src/example"));
+        assert!(matches!(unreadable, Err(InputError::Unreadable(_, _))));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        let dir = PathBuf::from("./inputs");
+        assert_eq!(input_dir(None, Some(dir.clone())), dir);
+        assert_eq!(
+            input_dir(Some("a".into()), Some(dir.clone())),
+            Path::new("a")
+        );
+        assert!(input_dir(None, None).ends_with("src/input"));
+
+        let sets = input_sets(&dir, &[], false).unwrap();
+        assert_eq!(sets[0].path(7), Path::new("./inputs/day_07.txt"));
+        let profiles = ["zoey".to_string(), "felix".to_string()];
+        let sets = input_sets(&dir, &profiles, false).unwrap();
+        assert_eq!(sets[0].profile.as_deref(), Some("felix"));
+        assert_eq!(sets[1].path(12), Path::new("./inputs/zoey/day_12.txt"));
+    }
+}
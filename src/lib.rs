@@ -0,0 +1,57 @@
+//! Solutions for Advent of Code 2024, one module per day, along with the runner the `aoc` binary
+//! is built from.
+//!
+//! Every day exposes its `parse` function and its parts on their own, e.g. [`day07::part_2`], as
+//! well as a [`solution::Solution`] implementation tying them together:
+//!
+//! ```
+//! use aoc::day01::Day01;
+//! use aoc::solution::Solution;
+//!
+//! let mut input = Day01::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+//! assert_eq!(Day01::part_1(&mut input).into(), aoc::answer::Answer::Integer(11));
+//! ```
+
+#![feature(extract_if)]
+#![feature(let_chains)]
+#![feature(slice_take)]
+
+pub mod allocs;
+pub mod answer;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod budget;
+pub mod cli;
+pub mod client;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod helpers;
+pub mod input;
+pub mod logger;
+pub mod output;
+pub mod readme;
+pub mod runner;
+pub mod scaffold;
+mod sections;
+pub mod solution;
+pub mod submit;
+pub mod theme;
+pub mod watch;
+
+use crate::allocs::PhaseAllocs;
+use crate::answer::Answer;
+use std::time::Duration;
+
+/// The answer to a part and how long it took, `None` when the part wasn't run
+pub type PartResult = Option<(Answer, Duration)>;
+/// Parse duration, the results of both parts and the heap usage of every phase of a day
+pub type DayResult = (Option<Duration>, PartResult, PartResult, PhaseAllocs);
@@ -0,0 +1,162 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::cell::Cell;
+
+/// Environment variable with the log filter, e.g. `debug` or `warn,aoc::day09=debug`
+pub const LOG_VAR: &str = "RUST_LOG";
+
+thread_local! {
+    /// Day and phase the current thread is working on, shown next to every message
+    static CONTEXT: Cell<(Option<usize>, Option<&'static str>)> = const { Cell::new((None, None)) };
+}
+
+/// Runs `f` as part of `day`, tagging everything it logs with the day
+pub fn in_day<T>(day: usize, f: impl FnOnce() -> T) -> T {
+    let previous = CONTEXT.get();
+    CONTEXT.set((Some(day), previous.1));
+    let result = f();
+    CONTEXT.set(previous);
+    result
+}
+
+/// Runs `f` as a phase of the current day, e.g. `parse` or `part 1`
+#[inline(always)]
+pub fn in_phase<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    let previous = CONTEXT.get();
+    CONTEXT.set((previous.0, Some(phase)));
+    let result = f();
+    CONTEXT.set(previous);
+    result
+}
+
+/// Log levels per module, the most specific matching module wins
+#[derive(Debug, PartialEq)]
+pub struct Filter {
+    default: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// Parses a comma-separated list of `level` and `module=level` directives, modules may leave
+    /// out the `aoc::` prefix
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut filter = Filter {
+            default: LevelFilter::Warn,
+            modules: Vec::new(),
+        };
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let level = |level: &str| {
+                level
+                    .parse::<LevelFilter>()
+                    .map_err(|_| format!("Invalid log level '{level}' in '{spec}'"))
+            };
+            match directive.split_once('=') {
+                Some((module, lvl)) => {
+                    let module = match module.starts_with("aoc") {
+                        true => module.to_string(),
+                        false => format!("aoc::{module}"),
+                    };
+                    filter.modules.push((module, level(lvl)?));
+                }
+                None => filter.default = level(directive)?,
+            }
+        }
+        // Longest modules first so the most specific one matches first
+        filter
+            .modules
+            .sort_by_key(|(module, _)| usize::MAX - module.len());
+        Ok(filter)
+    }
+
+    /// Shows warnings by default, every `--verbose` shows one more level
+    pub fn verbosity(verbose: u8) -> Filter {
+        let default = match verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        Filter {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    pub fn level(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .find(|(module, _)| target == module || target.starts_with(&format!("{module}::")))
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+/// Writes log messages to stderr, prefixed with the day and phase that emitted them
+pub struct Logger {
+    filter: Filter,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let context = match CONTEXT.get() {
+            (Some(day), Some(phase)) => format!("day {day} {phase}"),
+            (Some(day), None) => format!("day {day}"),
+            // Threads of the rayon pool don't know their day, the module tells it instead
+            _ => record.target().to_string(),
+        };
+        eprintln!("[{:<5} {context}] {}", record.level(), record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger, `--verbose` takes precedence over the filter in `RUST_LOG`. Messages more
+/// detailed than the compile time maximum, `warn` in release builds, never show up.
+pub fn init(verbose: u8, env_filter: Option<String>) -> Result<(), String> {
+    let filter = match env_filter {
+        Some(spec) if verbose == 0 => Filter::parse(&spec)?,
+        _ => Filter::verbosity(verbose),
+    };
+    log::set_max_level(filter.max_level());
+    log::set_logger(Box::leak(Box::new(Logger { filter }))).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter = Filter::parse("info,day09=debug,aoc::day09::defrag=trace").unwrap();
+        assert_eq!(filter.level("aoc::runner"), LevelFilter::Info);
+        assert_eq!(filter.level("aoc::day09"), LevelFilter::Debug);
+        assert_eq!(filter.level("aoc::day09::defrag"), LevelFilter::Trace);
+        assert_eq!(filter.level("aoc::day090"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+        assert_eq!(Filter::parse("").unwrap(), Filter::verbosity(0));
+        assert!(Filter::parse("day09=loud").is_err());
+    }
+
+    #[test]
+    fn test_context() {
+        in_day(9, || {
+            in_phase("part 2", || {
+                assert_eq!(CONTEXT.get(), (Some(9), Some("part 2")));
+            });
+            assert_eq!(CONTEXT.get(), (Some(9), None));
+        });
+        assert_eq!(CONTEXT.get(), (None, None));
+    }
+}
@@ -1,76 +1,378 @@
-#![feature(extract_if)]
-#![feature(let_chains)]
-#![feature(slice_take)]
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod helpers;
-
-use colored::Color::{Green, Red};
-use colored::*;
+use aoc::answer::Answer;
+use aoc::answers::{self, Answers};
+use aoc::baseline::{self, Baseline};
+use aoc::cli::{self, Args, Command, InputArgs};
+use aoc::client::{self, Client, Fetched};
+use aoc::input::{self, InputError, InputSet};
+use aoc::logger;
+use aoc::output::{self, Format};
+use aoc::runner::{self, DayRun, DayStatus, Schedule};
+use aoc::solution::{Day, DAYS};
+use aoc::submit::{self, Verdict};
+use aoc::theme::{self, Theme};
+use aoc::{readme, scaffold, watch};
 use std::fs;
-use std::time::Duration;
-
-type DayResult = (Option<Duration>, (String, Duration), (String, Duration));
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{env, process, thread};
 
-fn run_day(day: usize, func: fn(&str) -> DayResult, color: Color) -> Duration {
-    // Load the file before calling the function for accurate timing
-    let contents =
-        fs::read_to_string(format!("./src/input/day_{:0>2}.txt", day)).expect("File not found.");
+/// Picks the file recorded next to an input set's inputs, e.g. answers or baselines. Examples and
+/// explicit inputs don't have a set of their own, so they only use an explicitly given file.
+fn set_file(
+    explicit: &Option<PathBuf>,
+    set: &InputSet,
+    args: &Args,
+    default: fn(&Path) -> PathBuf,
+) -> Option<PathBuf> {
+    match explicit {
+        Some(path) => Some(path.clone()),
+        None if args.example || args.input.is_some() => None,
+        None => Some(default(&set.dir)),
+    }
+}
 
-    let (parse_duration, (p1, p1_duration), (p2, p2_duration)) = func(&contents);
-    let mut total_duration = p1_duration + p2_duration;
-    if let Some(p) = parse_duration {
-        total_duration += p;
+/// Answers to check a day against, the known example answers or the recorded ones
+fn expected_answers(
+    d: &Day,
+    answers: &Option<Answers>,
+    args: &Args,
+) -> Option<[Option<Answer>; 2]> {
+    match answers {
+        _ if args.example => Some(d.example_answers.map(|e| e.map(Answer::parse))),
+        Some(answers) if !args.record => Some(answers.expected(d.day)),
+        _ => None,
     }
+}
 
-    let title = match color {
-        Red => format!("🎄Day {day} ({total_duration:?}) 🎄\n~~~~~~~~~~~~~~~~~~~~~").bright_red(),
-        Green => {
-            format!("🎄Day {day} ({total_duration:?}) 🎄\n~~~~~~~~~~~~~~~~~~~~~").bright_green()
+/// The input set a subcommand works on, resolved the same way as the input sets of a run
+fn command_set(inputs: &InputArgs) -> InputSet {
+    let env_dir = env::var_os(input::INPUT_DIR_VAR).map(PathBuf::from);
+    let dir = input::input_dir(inputs.input_dir.clone(), env_dir);
+    let profiles = Vec::from_iter(inputs.profile.clone());
+    match input::input_sets(&dir, &profiles, false) {
+        Ok(mut sets) => sets.remove(0),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(2);
         }
-        _ => format!("🎄Day {day} ({total_duration:?}) 🎄\n~~~~~~~~~~~~~~~~~~~~~").white(),
-    };
-    println!("{title}");
-    if let Some(p) = parse_duration {
-        println!("Parse : ({p:?})");
     }
-    print!("{}", "Part 1: ".white());
-    print!("{}", p1.as_str().bold().white());
-    println!(" ({p1_duration:?})");
-    print!("{}", "Part 2: ".white());
-    print!("{}", p2.as_str().bold().white());
-    println!(" ({p2_duration:?})\n");
-    total_duration
 }
 
 fn main() {
-    let mut final_runtime = Duration::new(0, 0);
-    final_runtime += run_day(1, day01::run, Red);
-    final_runtime += run_day(2, day02::run, Green);
-    final_runtime += run_day(3, day03::run, Red);
-    final_runtime += run_day(4, day04::run, Green);
-    final_runtime += run_day(5, day05::run, Red);
-    final_runtime += run_day(6, day06::run, Green);
-    final_runtime += run_day(7, day07::run, Red);
-    final_runtime += run_day(8, day08::run, Green);
-    //final_runtime += run_day(9, day09::run, Red);
-    final_runtime += run_day(10, day10::run, Green);
+    let implemented = DAYS.map(|d| d.day);
+    let args = match cli::parse_args(env::args().skip(1), &implemented) {
+        Ok(Command::Run(args)) => *args,
+        Ok(Command::Scaffold(day)) => {
+            match scaffold::scaffold(day, Path::new(env!("CARGO_MANIFEST_DIR"))) {
+                Ok(files) => files.iter().for_each(|f| println!("Wrote {}", f.display())),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(2);
+                }
+            }
+            return;
+        }
+        Ok(Command::Fetch(day, inputs)) => {
+            match client::fetch(day, &command_set(&inputs), Client::from_env) {
+                Ok(Fetched::Downloaded(path)) => println!("Downloaded {}", path.display()),
+                Ok(Fetched::Cached(path)) => println!("Already cached at {}", path.display()),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(Command::Submit(day, part, inputs)) => {
+            let d = DAYS
+                .iter()
+                .find(|d| d.day == day)
+                .expect("day is implemented");
+            match submit::submit(d, part, &command_set(&inputs), Client::from_env) {
+                Ok((answer, verdict)) => {
+                    println!("Day {day} part {part}: {answer} is {verdict}");
+                    if !matches!(verdict, Verdict::Correct | Verdict::AlreadySolved) {
+                        process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(Command::Profile {
+            day,
+            part,
+            iterations,
+            inputs,
+        }) => {
+            let d = DAYS
+                .iter()
+                .find(|d| d.day == day)
+                .expect("day is implemented");
+            let input = match input::read_input(&command_set(&inputs).path(day)) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            };
+            let total = (d.profile)(&input, part, iterations);
+            println!(
+                "Day {day} part {part}: {iterations} iterations in {total:?}, {:?} per iteration",
+                total.div_f64(iterations as f64)
+            );
+            return;
+        }
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = logger::init(args.verbose, env::var(logger::LOG_VAR).ok()) {
+        eprintln!("{e}");
+        process::exit(2);
+    }
+    let theme = Theme::resolve(
+        args.theme.clone(),
+        env::var(theme::THEME_VAR).ok(),
+        io::stdout().is_terminal(),
+        env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+    );
+    let theme = match theme {
+        Ok(theme) => theme,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(2);
+        }
+    };
+    // The theme already settled whether there are colors, even for output that isn't a terminal
+    colored::control::set_override(!theme.colors.is_empty());
+    // Shared with the threads the days run on
+    let args = Arc::new(args);
+
+    let env_dir = env::var_os(input::INPUT_DIR_VAR).map(PathBuf::from);
+    let input_dir = input::input_dir(args.input_dir.clone(), env_dir);
+    let sets = match input::input_sets(&input_dir, &args.profiles, args.all_profiles) {
+        Ok(sets) => sets,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(2);
+        }
+    };
 
-    print!("{}", "Final Runtime: ".to_string().bold().white());
-    if final_runtime < Duration::new(0, 800_000_000) {
-        println!("{}", format!("{final_runtime:?}\n").bold().green());
-    } else if final_runtime < Duration::new(0, 0) {
-        println!("{}", format!("{final_runtime:?}\n").bold().yellow());
+    // Examples and explicit inputs don't belong to anyone, so they replace the profiles
+    let sets = if args.example {
+        vec![InputSet {
+            profile: Some("example".to_string()),
+            dir: input::example_dir(),
+        }]
+    } else if let Some(path) = &args.input {
+        let name = match path.to_str() {
+            Some(input::STDIN) => "stdin".to_string(),
+            _ => path.display().to_string(),
+        };
+        vec![InputSet {
+            profile: Some(name),
+            dir: input_dir,
+        }]
     } else {
-        println!("{}", format!("{final_runtime:?}\n").bold().red());
+        sets
+    };
+
+    if args.schedule == Schedule::Sequential {
+        // Spin up the thread pool first so the first day using it doesn't pay for it
+        rayon::broadcast(|_| ());
+    }
+
+    let mut runs = Vec::new();
+    let mut watched = None;
+    for set in &sets {
+        let answers_path = set_file(&args.answers, set, &args, answers::answers_path);
+        let mut answers = match answers_path.as_deref().map(Answers::load).transpose() {
+            Ok(answers) => answers.flatten(),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(2);
+            }
+        };
+        let baseline_path = set_file(&args.baseline, set, &args, baseline::baseline_path);
+        let mut baseline = match baseline_path.as_deref().map(Baseline::load).transpose() {
+            Ok(baseline) => baseline.flatten(),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(2);
+            }
+        };
+        let solve = |d: &Day| {
+            let status = match runner::load_inputs(d, set, &args) {
+                Ok(inputs) => {
+                    let expected = expected_answers(d, &answers, &args);
+                    let timings = baseline.as_ref().map(|b| b.timings(d.day));
+                    let (d, args) = (*d, Arc::clone(&args));
+                    runner::isolate(d.day, args.timeout, move || {
+                        runner::run_day(&d, &inputs, expected, timings, &args)
+                    })
+                    .map_or_else(DayStatus::Interrupted, |s| DayStatus::Solved(Box::new(s)))
+                }
+                // Only inputs the user didn't explicitly ask for are optional
+                Err(e @ InputError::Missing(_)) if args.input.is_none() => DayStatus::Skipped(e),
+                Err(e) => DayStatus::Failed(e),
+            };
+            DayRun {
+                day: d.day,
+                profile: set.profile.clone(),
+                status,
+            }
+        };
+        let print = |run: &DayRun| {
+            if args.format == Format::Pretty {
+                output::print_day(run, &theme);
+            }
+        };
+
+        let days = DAYS
+            .iter()
+            .filter(|d| args.days.contains(&d.day))
+            .collect::<Vec<_>>();
+        let set_runs = match args.schedule {
+            // Every day still times itself, the banners are printed in order once all are done
+            Schedule::Parallel => {
+                // Waiting on the days from rayon workers could starve the days' own parallel work
+                let set_runs = thread::scope(|s| {
+                    let handles = days
+                        .into_iter()
+                        .map(|d| s.spawn(|| solve(d)))
+                        .collect::<Vec<_>>();
+                    handles
+                        .into_iter()
+                        .map(|h| h.join().expect("days are isolated and don't panic"))
+                        .collect::<Vec<_>>()
+                });
+                set_runs.iter().for_each(print);
+                set_runs
+            }
+            Schedule::Sequential => days
+                .into_iter()
+                .map(|d| {
+                    let run = solve(d);
+                    print(&run);
+                    run
+                })
+                .collect(),
+        };
+
+        let mut recorded = 0;
+        for run in &set_runs {
+            let DayStatus::Solved(solved) = &run.status else {
+                continue;
+            };
+            if args.record {
+                let answers = answers.get_or_insert_with(Answers::default);
+                for p in &solved.parts {
+                    answers.record(run.day, p.part, &p.answer);
+                    recorded += 1;
+                }
+            }
+            if args.save_baseline {
+                baseline
+                    .get_or_insert_with(Baseline::default)
+                    .record(run.day, &solved.phases);
+            }
+        }
+        runs.extend(set_runs);
+        if args.watch {
+            // Watching is limited to a single day of a single input set
+            let d = DAYS.iter().find(|d| args.days.contains(&d.day));
+            watched = d.map(|d| (*d, expected_answers(d, &answers, &args)));
+        }
+        if let (true, Some(path), Some(answers)) = (args.record, &answers_path, &answers) {
+            match answers.save(path) {
+                Ok(_) => eprintln!("Recorded {recorded} answer(s) to {}", path.display()),
+                Err(e) => {
+                    eprintln!("Could not record answers to {}: {e}", path.display());
+                    process::exit(2);
+                }
+            }
+        }
+        if let (true, Some(path), Some(baseline)) = (args.save_baseline, &baseline_path, &baseline)
+        {
+            match baseline.save(path) {
+                Ok(_) => eprintln!("Saved baseline timings to {}", path.display()),
+                Err(e) => {
+                    eprintln!("Could not save baseline to {}: {e}", path.display());
+                    process::exit(2);
+                }
+            }
+        }
+    }
+
+    match args.format {
+        Format::Pretty => output::print_summary(&runs, &args.budget),
+        Format::Plain => print!("{}", output::plain(&runs)),
+        Format::Json => print!("{}", output::json(&runs)),
+        Format::Csv => print!("{}", output::csv(&runs)),
+        Format::Junit => print!("{}", output::junit(&runs)),
+        Format::Tap => print!("{}", output::tap(&runs)),
+    }
+
+    let failed = runs.iter().any(|run| match &run.status {
+        DayStatus::Solved(s) => {
+            s.mismatches() > 0 || (args.fail_on_regression && s.regressions() > 0)
+        }
+        DayStatus::Skipped(_) => false,
+        DayStatus::Failed(_) | DayStatus::Interrupted(_) => true,
+    });
+    if args.readme {
+        let path = readme::readme_path();
+        let skipped = runs
+            .iter()
+            .filter(|run| matches!(run.status, DayStatus::Skipped(_)))
+            .map(|run| run.day.to_string())
+            .collect::<Vec<_>>();
+        if failed {
+            eprintln!("Not updating {}, the run failed", path.display());
+        } else if !skipped.is_empty() {
+            eprintln!(
+                "Not updating {}, day(s) {} had no input",
+                path.display(),
+                skipped.join(", ")
+            );
+            process::exit(1);
+        } else {
+            let updated = fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read {}: {e}", path.display()))
+                .and_then(|contents| {
+                    readme::update(&contents, &readme::results(&runs, args.redact))
+                })
+                .and_then(|contents| {
+                    fs::write(&path, contents)
+                        .map_err(|e| format!("Could not write {}: {e}", path.display()))
+                });
+            match updated {
+                Ok(_) => eprintln!("Updated the results in {}", path.display()),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(2);
+                }
+            }
+        }
+    }
+    if let Some((d, expected)) = watched {
+        let previous = match runs.pop().map(|run| run.status) {
+            Some(DayStatus::Solved(solved)) => Some(*solved),
+            _ => None,
+        };
+        watch::watch(d, &sets[0], &args, &theme, expected, previous);
+    }
+    if failed {
+        process::exit(1);
     }
 }
@@ -0,0 +1,623 @@
+use crate::answer::Answer;
+use crate::budget::{Budget, Usage};
+use crate::runner::{Check, DayRun, DayStatus, Solved};
+use crate::theme::Theme;
+use colored::*;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How the results of a run are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Festive banners printed as each day finishes
+    Pretty,
+    /// One uncolored line per day and part
+    Plain,
+    Json,
+    Csv,
+    /// JUnit XML with one test case per day and part, for test dashboards
+    Junit,
+    /// Test Anything Protocol, one test per day and part
+    Tap,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "pretty" => Ok(Format::Pretty),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "junit" => Ok(Format::Junit),
+            "tap" => Ok(Format::Tap),
+            _ => Err(format!(
+                "Invalid format '{s}', expected pretty, plain, json, csv, junit or tap"
+            )),
+        }
+    }
+}
+
+fn label(run: &DayRun) -> String {
+    match &run.profile {
+        Some(profile) => format!("{} [{profile}]", run.day),
+        None => run.day.to_string(),
+    }
+}
+
+/// Prints the banner of a solved day, skipped and failed days only show up in the summary
+pub fn print_day(run: &DayRun, theme: &Theme) {
+    let DayStatus::Solved(solved) = &run.status else {
+        return;
+    };
+    let title = format!("Day {} ({:?})", label(run), solved.duration);
+    println!("{}", theme.banner(run.day, &title));
+    if let Some(p) = solved.run[0] {
+        match solved.allocs[0] {
+            Some(allocs) => println!("Parse : ({p:?}, {allocs})"),
+            None => println!("Parse : ({p:?})"),
+        }
+    }
+    for p in &solved.parts {
+        print!("{}", format!("Part {}: ", p.part).white());
+        match &p.answer {
+            // Grids are read from the terminal, so start them on a line of their own
+            Answer::Grid(_) => print!("\n{}", p.answer.to_string().bold().white()),
+            answer => print!("{}", answer.to_string().bold().white()),
+        }
+        match (solved.run[p.part], solved.allocs[p.part]) {
+            (Some(duration), Some(allocs)) => print!(" ({duration:?}, {allocs})"),
+            (Some(duration), None) => print!(" ({duration:?})"),
+            _ => (),
+        }
+        match &p.check {
+            Check::Pass => print!(" {}", theme.pass_mark().green()),
+            Check::Fail { expected } => {
+                print!(
+                    " {}",
+                    format!("{} expected {expected}", theme.fail_mark()).red()
+                )
+            }
+            Check::Unknown => print!(" {}", "? no expected answer".yellow()),
+            Check::Unchecked => (),
+        }
+        println!();
+    }
+    if let Some(result) = &solved.bench {
+        print!("{result}");
+    }
+    if !solved.deltas.is_empty() {
+        println!("Baseline:");
+    }
+    for delta in &solved.deltas {
+        if delta.regression {
+            println!("{}", format!("{delta} regression").red());
+        } else {
+            println!("{delta}");
+        }
+    }
+    println!();
+}
+
+fn summary_label(run: &DayRun) -> String {
+    match &run.profile {
+        Some(profile) => format!("Day {:<2} [{profile}]: ", run.day),
+        None => format!("Day {:<2}: ", run.day),
+    }
+}
+
+/// Prints the status of every day, followed by how much of the time budget each day used
+pub fn print_summary(runs: &[DayRun], budget: &Budget) {
+    println!("{}", "Summary\n~~~~~~~".bold().white());
+    for run in runs {
+        let label = summary_label(run);
+        match &run.status {
+            DayStatus::Solved(s) => {
+                let duration = s.duration;
+                let (mismatches, regressions) = (s.mismatches(), s.regressions());
+                if mismatches > 0 {
+                    println!(
+                        "{label}{}, {mismatches} part(s) differ from the expected answers ({duration:?})",
+                        "mismatch".red()
+                    );
+                } else if regressions > 0 {
+                    println!(
+                        "{label}{}, {regressions} phase(s) slower than the baseline ({duration:?})",
+                        "regressed".yellow()
+                    );
+                } else {
+                    println!("{label}{} ({duration:?})", "ok".green());
+                }
+            }
+            DayStatus::Skipped(e) => println!("{label}{}, {e}", "skipped".yellow()),
+            DayStatus::Failed(e) => println!("{label}{}, {e}", "failed".red()),
+            DayStatus::Interrupted(e) => println!("{label}{}", e.to_string().red()),
+        }
+    }
+
+    println!();
+
+    println!(
+        "{}",
+        format!("Budget ({:?})\n~~~~~~", budget.total)
+            .bold()
+            .white()
+    );
+    for run in runs {
+        let DayStatus::Solved(s) = &run.status else {
+            continue;
+        };
+        let line = format!(
+            "{}{:>11.1?} {:>6.1}%",
+            summary_label(run),
+            s.duration,
+            budget.share(s.duration)
+        );
+        match (
+            budget.day_usage(run.day, s.duration),
+            budget.day_budget(run.day),
+        ) {
+            (Usage::Over, Some(day_budget)) => {
+                println!(
+                    "{}",
+                    format!("{line}  over its {day_budget:?} budget").red()
+                )
+            }
+            _ => println!("{line}"),
+        }
+    }
+
+    let final_runtime: Duration = runs.iter().map(|r| r.duration()).sum();
+    print!("{}", "Final Runtime: ".to_string().bold().white());
+    let runtime = format!(
+        "{final_runtime:?} ({:.1}% of {:?})\n",
+        budget.share(final_runtime),
+        budget.total
+    );
+    match budget.total_usage(final_runtime) {
+        Usage::Within => println!("{}", runtime.bold().green()),
+        Usage::Warning => println!("{}", runtime.bold().yellow()),
+        Usage::Over => println!("{}", runtime.bold().red()),
+    }
+}
+
+fn check_name(check: &Check) -> &'static str {
+    match check {
+        Check::Unchecked => "unchecked",
+        Check::Unknown => "unknown",
+        Check::Pass => "pass",
+        Check::Fail { .. } => "fail",
+    }
+}
+
+fn message(run: &DayRun) -> Option<String> {
+    match &run.status {
+        DayStatus::Skipped(e) | DayStatus::Failed(e) => Some(e.to_string()),
+        DayStatus::Interrupted(e) => Some(e.to_string()),
+        DayStatus::Solved(_) => None,
+    }
+}
+
+fn nanos(duration: Option<Duration>) -> Option<u128> {
+    duration.map(|d| d.as_nanos())
+}
+
+/// One line per day followed by a line per part, without any colors or decorations
+pub fn plain(runs: &[DayRun]) -> String {
+    let mut out = String::new();
+    for run in runs {
+        let day = label(run);
+        match &run.status {
+            DayStatus::Solved(s) => {
+                write!(out, "Day {day}: {} ({:?}", run.status_name(), s.duration).unwrap();
+                if let Some(parse) = s.phases[0] {
+                    write!(out, ", parse {parse:?}").unwrap();
+                }
+                writeln!(out, ")").unwrap();
+                for p in &s.parts {
+                    write!(out, "Day {day} Part {}: {}", p.part, p.answer).unwrap();
+                    if let Some(duration) = s.phases[p.part] {
+                        write!(out, " ({duration:?})").unwrap();
+                    }
+                    match &p.check {
+                        Check::Fail { expected } => write!(out, " fail, expected {expected}"),
+                        Check::Unchecked => Ok(()),
+                        check => write!(out, " {}", check_name(check)),
+                    }
+                    .unwrap();
+                    writeln!(out).unwrap();
+                }
+            }
+            _ => {
+                let msg = message(run).unwrap_or_default();
+                writeln!(out, "Day {day}: {}, {msg}", run.status_name()).unwrap();
+            }
+        }
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_opt<T: ToString>(value: Option<T>) -> String {
+    value.map_or("null".to_string(), |v| v.to_string())
+}
+
+fn json_parts(s: &Solved) -> String {
+    let parts = s
+        .parts
+        .iter()
+        .map(|p| {
+            let expected = match &p.check {
+                Check::Fail { expected } => json_string(&expected.to_string()),
+                _ => "null".to_string(),
+            };
+            format!(
+                "{{\"part\":{},\"answer\":{},\"duration_ns\":{},\"allocations\":{},\"peak_bytes\":{},\"check\":\"{}\",\"expected\":{expected}}}",
+                p.part,
+                json_string(&p.answer.to_string()),
+                json_opt(nanos(s.phases[p.part])),
+                json_opt(s.allocs[p.part].map(|a| a.count)),
+                json_opt(s.allocs[p.part].map(|a| a.peak_bytes)),
+                check_name(&p.check),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", parts.join(","))
+}
+
+/// A single JSON document with one object per day run, durations are in nanoseconds
+pub fn json(runs: &[DayRun]) -> String {
+    let days = runs
+        .iter()
+        .map(|run| {
+            let (parse, parts) = match &run.status {
+                DayStatus::Solved(s) => (nanos(s.phases[0]), json_parts(s)),
+                _ => (None, "[]".to_string()),
+            };
+            format!(
+                "{{\"day\":{},\"profile\":{},\"status\":\"{}\",\"message\":{},\"duration_ns\":{},\"parse_ns\":{},\"parts\":{parts}}}",
+                run.day,
+                json_opt(run.profile.as_deref().map(json_string)),
+                run.status_name(),
+                json_opt(message(run).as_deref().map(json_string)),
+                run.duration().as_nanos(),
+                json_opt(parse),
+            )
+        })
+        .collect::<Vec<_>>();
+    let total: Duration = runs.iter().map(|r| r.duration()).sum();
+    format!(
+        "{{\"days\":[{}],\"total_ns\":{}}}\n",
+        days.join(","),
+        total.as_nanos()
+    )
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// One row per day and part, days that didn't run get a single row without a part
+pub fn csv(runs: &[DayRun]) -> String {
+    let mut out =
+        String::from("day,profile,status,part,answer,check,parse_ns,duration_ns,message\n");
+    let opt = |v: Option<u128>| v.map(|v| v.to_string()).unwrap_or_default();
+    for run in runs {
+        let profile = csv_field(run.profile.as_deref().unwrap_or_default());
+        match &run.status {
+            DayStatus::Solved(s) => {
+                for p in &s.parts {
+                    writeln!(
+                        out,
+                        "{},{profile},{},{},{},{},{},{},",
+                        run.day,
+                        run.status_name(),
+                        p.part,
+                        csv_field(&p.answer.to_string()),
+                        check_name(&p.check),
+                        opt(nanos(s.phases[0])),
+                        opt(nanos(s.phases[p.part])),
+                    )
+                    .unwrap();
+                }
+            }
+            _ => {
+                let msg = csv_field(&message(run).unwrap_or_default());
+                writeln!(
+                    out,
+                    "{},{profile},{},,,,,,{msg}",
+                    run.day,
+                    run.status_name()
+                )
+                .unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// A test case of the JUnit and TAP reports
+struct Case {
+    /// The day, and its profile if it has one
+    suite: String,
+    name: String,
+    duration: Option<Duration>,
+    answer: Option<String>,
+    outcome: Outcome,
+}
+
+enum Outcome {
+    Pass,
+    /// The answer doesn't match the expected one
+    Failure(String),
+    /// The day failed to run, e.g. it panicked or timed out
+    Error(String),
+    Skipped(String),
+}
+
+/// One case per part of a solved day, days that didn't run get a single case for the whole day
+fn cases(runs: &[DayRun]) -> Vec<Case> {
+    let mut cases = Vec::new();
+    for run in runs {
+        let suite = format!("day {}", label(run));
+        let DayStatus::Solved(s) = &run.status else {
+            let msg = message(run).unwrap_or_default();
+            cases.push(Case {
+                suite,
+                name: "run".to_string(),
+                duration: None,
+                answer: None,
+                outcome: match run.status {
+                    DayStatus::Skipped(_) => Outcome::Skipped(msg),
+                    _ => Outcome::Error(msg),
+                },
+            });
+            continue;
+        };
+        for p in &s.parts {
+            cases.push(Case {
+                suite: suite.clone(),
+                name: format!("part {}", p.part),
+                duration: s.phases[p.part],
+                answer: Some(p.answer.to_string()),
+                outcome: match &p.check {
+                    Check::Fail { expected } => {
+                        Outcome::Failure(format!("expected {expected}, got {}", p.answer))
+                    }
+                    _ => Outcome::Pass,
+                },
+            });
+        }
+    }
+    cases
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A JUnit XML report with a test suite per day, durations are in seconds
+pub fn junit(runs: &[DayRun]) -> String {
+    let cases = cases(runs);
+    let count =
+        |cases: &[&Case], f: fn(&Outcome) -> bool| cases.iter().filter(|c| f(&c.outcome)).count();
+    let stats = |cases: &[&Case]| {
+        let time: Duration = cases.iter().filter_map(|c| c.duration).sum();
+        format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\"",
+            cases.len(),
+            count(cases, |o| matches!(o, Outcome::Failure(_))),
+            count(cases, |o| matches!(o, Outcome::Error(_))),
+            count(cases, |o| matches!(o, Outcome::Skipped(_))),
+            time.as_secs_f64()
+        )
+    };
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        out,
+        "<testsuites name=\"aoc\" {}>",
+        stats(&cases.iter().collect::<Vec<_>>())
+    )
+    .unwrap();
+    for suite in cases.chunk_by(|a, b| a.suite == b.suite) {
+        let name = xml_escape(&suite[0].suite);
+        writeln!(
+            out,
+            "  <testsuite name=\"{name}\" {}>",
+            stats(&suite.iter().collect::<Vec<_>>())
+        )
+        .unwrap();
+        for case in suite {
+            let time = case.duration.unwrap_or_default().as_secs_f64();
+            writeln!(
+                out,
+                "    <testcase classname=\"{name}\" name=\"{}\" time=\"{time:.6}\">",
+                case.name
+            )
+            .unwrap();
+            match &case.outcome {
+                Outcome::Pass => {}
+                Outcome::Failure(msg) => {
+                    writeln!(out, "      <failure message=\"{}\"/>", xml_escape(msg)).unwrap()
+                }
+                Outcome::Error(msg) => {
+                    writeln!(out, "      <error message=\"{}\"/>", xml_escape(msg)).unwrap()
+                }
+                Outcome::Skipped(msg) => {
+                    writeln!(out, "      <skipped message=\"{}\"/>", xml_escape(msg)).unwrap()
+                }
+            }
+            if let Some(answer) = &case.answer {
+                writeln!(out, "      <system-out>{}</system-out>", xml_escape(answer)).unwrap();
+            }
+            writeln!(out, "    </testcase>").unwrap();
+        }
+        writeln!(out, "  </testsuite>").unwrap();
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// A TAP version 13 report, every test is followed by a YAML block with its answer and duration
+pub fn tap(runs: &[DayRun]) -> String {
+    let cases = cases(runs);
+    let mut out = format!("TAP version 13\n1..{}\n", cases.len());
+    for (i, case) in cases.iter().enumerate() {
+        let status = match case.outcome {
+            Outcome::Pass | Outcome::Skipped(_) => "ok",
+            Outcome::Failure(_) | Outcome::Error(_) => "not ok",
+        };
+        write!(out, "{status} {} - {} {}", i + 1, case.suite, case.name).unwrap();
+        if let Outcome::Skipped(msg) = &case.outcome {
+            write!(out, " # SKIP {msg}").unwrap();
+        }
+        writeln!(out, "\n  ---").unwrap();
+        if let Some(answer) = &case.answer {
+            writeln!(out, "  answer: {}", json_string(answer)).unwrap();
+        }
+        if let Some(duration) = case.duration {
+            writeln!(out, "  duration_ms: {}", duration.as_secs_f64() * 1000.0).unwrap();
+        }
+        if let Outcome::Failure(msg) | Outcome::Error(msg) = &case.outcome {
+            writeln!(out, "  message: {}", json_string(msg)).unwrap();
+        }
+        writeln!(out, "  ...").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::input::InputError;
+    use crate::runner::{self, Interrupted};
+    use std::path::PathBuf;
+
+    fn runs() -> Vec<DayRun> {
+        let phases = [
+            Some(Duration::from_nanos(29_000)),
+            Some(Duration::from_nanos(55_800)),
+            None,
+        ];
+        vec![
+            DayRun {
+                day: 1,
+                profile: None,
+                status: DayStatus::Solved(Box::new(runner::solved(
+                    phases,
+                    vec![(Answer::from("a,\"b\""), Check::Pass)],
+                ))),
+            },
+            DayRun {
+                day: 9,
+                profile: Some("zoey".to_string()),
+                status: DayStatus::Skipped(InputError::Missing(PathBuf::from("day_09.txt"))),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            json(&runs()),
+            "{\"days\":[\
+            {\"day\":1,\"profile\":null,\"status\":\"ok\",\"message\":null,\"duration_ns\":84800,\"parse_ns\":29000,\
+            \"parts\":[{\"part\":1,\"answer\":\"a,\\\"b\\\"\",\"duration_ns\":55800,\"allocations\":null,\"peak_bytes\":null,\"check\":\"pass\",\"expected\":null}]},\
+            {\"day\":9,\"profile\":\"zoey\",\"status\":\"skipped\",\"message\":\"no input at day_09.txt\",\"duration_ns\":0,\"parse_ns\":null,\"parts\":[]}\
+            ],\"total_ns\":84800}\n"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = csv(&runs());
+        let rows = csv.lines().collect::<Vec<_>>();
+        assert_eq!(rows[1], "1,,ok,1,\"a,\"\"b\"\"\",pass,29000,55800,");
+        assert_eq!(rows[2], "9,zoey,skipped,,,,,,no input at day_09.txt");
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    fn failing_runs() -> Vec<DayRun> {
+        let mut runs = runs();
+        if let DayStatus::Solved(s) = &mut runs[0].status {
+            s.parts[0].check = Check::Fail {
+                expected: Answer::from(11),
+            };
+        }
+        runs.push(DayRun {
+            day: 10,
+            profile: None,
+            status: DayStatus::Interrupted(Interrupted::Panicked("<oops>".to_string())),
+        });
+        runs
+    }
+
+    #[test]
+    fn test_junit() {
+        let junit = junit(&failing_runs());
+        let lines = junit.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1],
+            "<testsuites name=\"aoc\" tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"1\" time=\"0.000056\">"
+        );
+        assert_eq!(
+            lines[2..6],
+            [
+                "  <testsuite name=\"day 1\" tests=\"1\" failures=\"1\" errors=\"0\" skipped=\"0\" time=\"0.000056\">",
+                "    <testcase classname=\"day 1\" name=\"part 1\" time=\"0.000056\">",
+                "      <failure message=\"expected 11, got a,&quot;b&quot;\"/>",
+                "      <system-out>a,&quot;b&quot;</system-out>",
+            ]
+        );
+        assert!(junit.contains("name=\"day 9 [zoey]\""));
+        assert!(junit.contains("<skipped message=\"no input at day_09.txt\"/>"));
+        assert!(junit.contains("<error message=\"panicked: &lt;oops&gt;\"/>"));
+    }
+
+    #[test]
+    fn test_tap() {
+        let tap = tap(&failing_runs());
+        assert!(tap.starts_with("TAP version 13\n1..3\nnot ok 1 - day 1 part 1\n  ---\n"));
+        assert!(tap.contains("  answer: \"a,\\\"b\\\"\"\n  duration_ms: 0.0558\n"));
+        assert!(tap.contains("ok 2 - day 9 [zoey] run # SKIP no input at day_09.txt\n"));
+        assert!(
+            tap.contains("not ok 3 - day 10 run\n  ---\n  message: \"panicked: <oops>\"\n  ...\n")
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::runner::{DayRun, DayStatus};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Markers around the generated results in the README, everything in between is replaced
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+
+/// Shown instead of the answers when they shouldn't be published
+const REDACTED: &str = "*****";
+
+/// The README of the crate, independent of the working directory
+pub fn readme_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("README.md")
+}
+
+/// Renders the results of the solved days as the README shows them, a total runtime followed by
+/// the parse and part timings of every day.
+pub fn results(runs: &[DayRun], redact: bool) -> String {
+    let total: Duration = runs.iter().map(|r| r.duration()).sum();
+    let mut out = format!("**Total Runtime: {total:.1?}**\n\n");
+    out.push_str("| Day | Parse | Part 1 | Part 2 | Total |\n");
+    out.push_str("|----:|------:|-------:|-------:|------:|\n");
+    for run in runs {
+        let DayStatus::Solved(solved) = &run.status else {
+            continue;
+        };
+        let parse = solved.phases[0].map_or("-".to_string(), |p| format!("{p:.1?}"));
+        let parts = [1, 2].map(|part| match solved.parts.iter().find(|p| p.part == part) {
+            Some(p) => {
+                // Rows of grid answers can't span lines in a table
+                let answer = match redact {
+                    true => REDACTED.to_string(),
+                    false => p.answer.to_string().replace('\n', "<br>"),
+                };
+                match solved.phases[part] {
+                    Some(duration) => format!("`{answer}` ({duration:.1?})"),
+                    None => format!("`{answer}`"),
+                }
+            }
+            None => "-".to_string(),
+        });
+        writeln!(
+            out,
+            "| {} | {parse} | {} | {} | {:.1?} |",
+            run.day, parts[0], parts[1], solved.duration
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Replaces the results between the markers of `readme` with `results`
+pub fn update(readme: &str, results: &str) -> Result<String, String> {
+    let missing = || format!("README has no {START_MARKER} ... {END_MARKER} section");
+    let (head, rest) = readme.split_once(START_MARKER).ok_or_else(missing)?;
+    let (_, tail) = rest.split_once(END_MARKER).ok_or_else(missing)?;
+    Ok(format!("{head}{START_MARKER}\n{results}{END_MARKER}{tail}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::runner::{self, Check};
+
+    fn run(answer: &str) -> DayRun {
+        let phases = [
+            Some(Duration::from_micros(29)),
+            Some(Duration::from_nanos(55_840)),
+            None,
+        ];
+        DayRun {
+            day: 1,
+            profile: None,
+            status: DayStatus::Solved(Box::new(runner::solved(
+                phases,
+                vec![(Answer::parse(answer), Check::Unchecked)],
+            ))),
+        }
+    }
+
+    #[test]
+    fn test_results() {
+        let results = results(&[run("1151792")], false);
+        assert!(results.starts_with("**Total Runtime: 84.8µs**\n\n"));
+        assert!(results.ends_with("| 1 | 29.0µs | `1151792` (55.8µs) | - | 84.8µs |\n"));
+        assert!(!super::results(&[run("1151792")], true).contains("1151792"));
+    }
+
+    #[test]
+    fn test_update() {
+        let readme = format!("# Title\n{START_MARKER}\nstale\n{END_MARKER}\n# Next\n");
+        assert_eq!(
+            update(&readme, "fresh\n").unwrap(),
+            format!("# Title\n{START_MARKER}\nfresh\n{END_MARKER}\n# Next\n")
+        );
+        assert!(update("# Title\n", "fresh\n").is_err());
+    }
+}
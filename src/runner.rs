@@ -0,0 +1,307 @@
+use crate::allocs::PhaseAllocs;
+use crate::answer::Answer;
+use crate::baseline::{self, Delta, PhaseTimings};
+use crate::bench::{self, BenchResult};
+use crate::cli::{Args, Parts};
+use crate::input::{self, InputError, InputSet};
+use crate::logger;
+use crate::solution::Day;
+use log::{debug, warn};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Stack size of the threads the days run on, the same as the main thread gets on most platforms
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// How the days of an input set are scheduled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// One day after the other on a warmed up pool, printing each as it finishes. Timings aren't
+    /// skewed by other days, so this is the one to use for accurate numbers.
+    Sequential,
+    /// Days run concurrently on threads of their own, each timed on its own rather than by wall
+    /// clock
+    Parallel,
+}
+
+/// Result of checking a part's answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// Answers aren't being checked
+    Unchecked,
+    /// Answers are being checked but none is known for this part yet
+    Unknown,
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+}
+
+#[derive(Debug)]
+pub struct PartOutcome {
+    pub part: usize,
+    pub answer: Answer,
+    pub check: Check,
+}
+
+#[derive(Debug)]
+pub struct Solved {
+    /// Total runtime of the day, the sum of the median phases when benchmarking
+    pub duration: Duration,
+    /// Phase timings of the first run
+    pub run: PhaseTimings,
+    /// Phase timings the day is reported and compared with, medians when benchmarking
+    pub phases: PhaseTimings,
+    /// Heap usage of the phases of the first run, with the `alloc-stats` feature
+    pub allocs: PhaseAllocs,
+    pub parts: Vec<PartOutcome>,
+    pub bench: Option<BenchResult>,
+    pub deltas: Vec<Delta>,
+}
+
+impl Solved {
+    pub fn mismatches(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| matches!(p.check, Check::Fail { .. }))
+            .count()
+    }
+
+    pub fn regressions(&self) -> usize {
+        self.deltas.iter().filter(|d| d.regression).count()
+    }
+}
+
+/// A day solved in `phases` with the answers of its parts in order, for the tests of the reports
+#[cfg(test)]
+pub fn solved(phases: PhaseTimings, parts: Vec<(Answer, Check)>) -> Solved {
+    Solved {
+        duration: phases.iter().flatten().sum(),
+        run: phases,
+        phases,
+        allocs: [None; 3],
+        parts: parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, (answer, check))| PartOutcome {
+                part: i + 1,
+                answer,
+                check,
+            })
+            .collect(),
+        bench: None,
+        deltas: vec![],
+    }
+}
+
+/// Why a day didn't produce a result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interrupted {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interrupted::Panicked(message) => write!(f, "panicked: {message}"),
+            Interrupted::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+/// Outcome of a single day, collected for the summary at the end of a run
+#[derive(Debug)]
+pub enum DayStatus {
+    Solved(Box<Solved>),
+    Skipped(InputError),
+    Failed(InputError),
+    Interrupted(Interrupted),
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: usize,
+    pub profile: Option<String>,
+    pub status: DayStatus,
+}
+
+impl DayRun {
+    /// Short machine friendly name of the day's status
+    pub fn status_name(&self) -> &'static str {
+        match &self.status {
+            DayStatus::Solved(s) if s.mismatches() > 0 => "mismatch",
+            DayStatus::Solved(s) if s.regressions() > 0 => "regressed",
+            DayStatus::Solved(_) => "ok",
+            DayStatus::Skipped(_) => "skipped",
+            DayStatus::Failed(_) => "failed",
+            DayStatus::Interrupted(Interrupted::Panicked(_)) => "panicked",
+            DayStatus::Interrupted(Interrupted::TimedOut(_)) => "timeout",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match &self.status {
+            DayStatus::Solved(s) => s.duration,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Loads the inputs of a day up front so reading them doesn't count towards its timings, each
+/// paired with the parts it feeds
+pub fn load_inputs(
+    d: &Day,
+    set: &InputSet,
+    args: &Args,
+) -> Result<Vec<(String, Parts)>, InputError> {
+    if args.example {
+        d.example_inputs(args.parts)
+    } else {
+        let path = args.input.clone().unwrap_or_else(|| set.path(d.day));
+        debug!("Reading the input of day {} from {}", d.day, path.display());
+        input::read_input(&path).map(|c| vec![(c, args.parts)])
+    }
+}
+
+/// Runs a day over its inputs, each paired with the parts it feeds. Parts are checked against
+/// `expected` answers and phase timings against `baseline` when given.
+pub fn run_day(
+    d: &Day,
+    inputs: &[(String, Parts)],
+    expected: Option<[Option<Answer>; 2]>,
+    baseline: Option<PhaseTimings>,
+    args: &Args,
+) -> Solved {
+    logger::in_day(d.day, || {
+        let (parse_duration, p1, p2, allocs) = bench::run_inputs(d, inputs);
+        let run = [
+            parse_duration,
+            p1.as_ref().map(|p| p.1),
+            p2.as_ref().map(|p| p.1),
+        ];
+
+        let parts = [(1, p1), (2, p2)]
+            .into_iter()
+            .filter_map(|(part, result)| {
+                let (answer, _) = result?;
+                let check = match expected.as_ref().map(|e| &e[part - 1]) {
+                    Some(Some(e)) if *e == answer => Check::Pass,
+                    Some(Some(e)) => Check::Fail {
+                        expected: e.clone(),
+                    },
+                    Some(None) => Check::Unknown,
+                    None => Check::Unchecked,
+                };
+                Some(PartOutcome {
+                    part,
+                    answer,
+                    check,
+                })
+            })
+            .collect();
+
+        let bench = args
+            .bench
+            .map(|iterations| bench::bench(d, inputs, iterations, args.warmup));
+        let phases = match &bench {
+            Some(result) => {
+                [result.parse, result.part_1, result.part_2].map(|s| s.map(|s| s.median))
+            }
+            None => run,
+        };
+        let deltas = baseline
+            .map(|b| baseline::compare(&b, &phases, args.threshold))
+            .unwrap_or_default();
+        Solved {
+            duration: phases.iter().flatten().sum(),
+            run,
+            phases,
+            allocs,
+            parts,
+            bench,
+            deltas,
+        }
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic payload".to_string(),
+        },
+    }
+}
+
+/// Runs `f` on a thread of its own so a panicking or hanging day doesn't take the whole run with
+/// it. A day that runs over `timeout` can't be stopped, its thread is left behind to finish or
+/// die with the process while the run moves on.
+pub fn isolate<T, F>(day: usize, timeout: Option<Duration>, f: F) -> Result<T, Interrupted>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .name(format!("day {day}"))
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // Nobody is listening anymore once the day timed out
+            let _ = tx.send(f());
+        })
+        .expect("failed to spawn a thread for the day");
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(result) => {
+            let _ = handle.join();
+            Ok(result)
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            warn!("Day {day} is still running after {timeout:?}, leaving it behind");
+            Err(Interrupted::TimedOut(timeout.unwrap_or_default()))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Interrupted::Panicked(match handle.join() {
+            Err(payload) => panic_message(payload),
+            Ok(()) => "day finished without a result".to_string(),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolate() {
+        assert_eq!(isolate(1, None, || 42), Ok(42));
+        assert_eq!(
+            isolate(7, None, || -> usize { panic!("BIG NUMBER") }),
+            Err(Interrupted::Panicked("BIG NUMBER".to_string()))
+        );
+        assert_eq!(
+            isolate(7, None, || -> usize { panic!("{} is too big", 1u64 << 40) }),
+            Err(Interrupted::Panicked(
+                "1099511627776 is too big".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_isolate_timeout() {
+        let timeout = Duration::from_millis(10);
+        let result = isolate(6, Some(timeout), || thread::sleep(Duration::from_secs(5)));
+        assert_eq!(result, Err(Interrupted::TimedOut(timeout)));
+        assert_eq!(
+            isolate(6, Some(Duration::from_secs(5)), || "fast"),
+            Ok("fast")
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::input;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Width rustfmt wraps lines at
+const MAX_WIDTH: usize = 100;
+
+/// Module of a new day, with placeholder parts and ignored tests waiting for the example answers
+pub fn template(day: usize) -> String {
+    format!(
+        r#"//! Day {day}
+
+use crate::answer::Answer;
+use crate::solution::Solution;
+
+/// The day as a [`Solution`] built from [`parse`], [`part_1`] and [`part_2`]
+pub struct Day{day:0>2};
+
+impl Solution for Day{day:0>2} {{
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Self::Input {{
+        parse(input)
+    }}
+
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer> {{
+        part_1(input)
+    }}
+
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer> {{
+        part_2(input)
+    }}
+}}
+
+#[derive(Debug, Default, Clone)]
+pub struct Puzzle {{
+    pub lines: Vec<String>,
+}}
+
+/// Parses the puzzle input
+pub fn parse(input: &str) -> Puzzle {{
+    Puzzle {{
+        lines: input.lines().map(str::to_string).collect(),
+    }}
+}}
+
+/// Answer to part 1
+pub fn part_1(_p: &Puzzle) -> usize {{
+    0
+}}
+
+/// Answer to part 2
+pub fn part_2(_p: &Puzzle) -> usize {{
+    0
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::fs;
+
+    #[test]
+    #[ignore = "waiting for the example answer"]
+    fn test_part_one() {{
+        let input = fs::read_to_string("./src/example/{file}").expect("File not found.");
+        let x = parse(&input);
+        assert_eq!(part_1(&x), 0);
+    }}
+
+    #[test]
+    #[ignore = "waiting for the example answer"]
+    fn test_part_two() {{
+        let input = fs::read_to_string("./src/example/{file}").expect("File not found.");
+        let x = parse(&input);
+        assert_eq!(part_2(&x), 0);
+    }}
+}}
+"#,
+        file = input::file_name(day),
+    )
+}
+
+/// Declares the module of a new day in `lib.rs`, keeping the day modules in order
+pub fn register_module(lib: &str, day: usize) -> Result<String, String> {
+    let declaration = format!("pub mod day{day:0>2};");
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("Day {day} is already declared in lib.rs"));
+    }
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod day"))
+        .collect::<Vec<_>>();
+    let index = match days.iter().find(|(_, l)| **l > declaration.as_str()) {
+        Some((i, _)) => *i,
+        None => days.last().ok_or("lib.rs declares no days to add to")?.0 + 1,
+    };
+    lines.insert(index, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a new day to the `DAYS` registry and the imports of `solution.rs`
+pub fn register_day(solution: &str, day: usize) -> Result<String, String> {
+    let module = format!("day{day:0>2}");
+    let import_start = solution
+        .find("use crate::{")
+        .ok_or("solution.rs doesn't import the day modules")?;
+    let import_end = import_start + solution[import_start..].find("};").unwrap_or(0) + 2;
+    let import = &solution[import_start..import_end];
+    let mut modules = import["use crate::{".len()..import.len() - 2]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    if modules.contains(&module.as_str()) {
+        return Err(format!("Day {day} is already registered in solution.rs"));
+    }
+    modules.push(&module);
+    // Modules before other imports, the way rustfmt orders them
+    modules.sort_by_key(|m| (!m.starts_with("day"), m.to_string()));
+
+    let days = "pub const DAYS: [Day; ";
+    let days_start = solution
+        .find(days)
+        .ok_or("solution.rs has no DAYS registry")?;
+    let len_end = days_start + solution[days_start..].find(']').unwrap_or(0);
+    let len = solution[days_start + days.len()..len_end]
+        .parse::<usize>()
+        .map_err(|_| "Could not read the length of DAYS")?;
+    let registry_end = days_start + solution[days_start..].find("];").unwrap_or(0);
+    let entries = &solution[len_end + "] = [".len()..registry_end];
+    let mut entries = entries
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    entries.push(format!("    Day::new::<{module}::Day{day:0>2}>({day}),"));
+    entries.sort_by_key(|e| e.split("::<").nth(1).map(str::to_string));
+
+    Ok(format!(
+        "{}{}{}{days}{}] = [\n{}\n{}",
+        &solution[..import_start],
+        format_import(&modules),
+        &solution[import_end..days_start],
+        len + 1,
+        entries.join("\n"),
+        &solution[registry_end..]
+    ))
+}
+
+/// Lays out a `use crate::{...};` import like rustfmt, wrapping it when it gets too long
+fn format_import(modules: &[&str]) -> String {
+    let single = format!("use crate::{{{}}};", modules.join(", "));
+    // rustfmt leaves no room for a full width import
+    if single.len() < MAX_WIDTH {
+        return single;
+    }
+    let mut import = String::from("use crate::{\n");
+    let mut line = String::from("   ");
+    for module in modules {
+        if line.len() + module.len() + 2 > MAX_WIDTH {
+            import.push_str(&line);
+            import.push('\n');
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {module},"));
+    }
+    import.push_str(&line);
+    import.push_str("\n};");
+    import
+}
+
+/// Generates the module, an empty example and the registration of a new day in the crate at
+/// `root`, returning the files it created or changed
+pub fn scaffold(day: usize, root: &Path) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/day{day:0>2}.rs"));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    let example = root.join("src/example").join(input::file_name(day));
+    let lib = root.join("src/lib.rs");
+    let solution = root.join("src/solution.rs");
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+    };
+    // Work out every change before writing anything so a failure leaves the crate untouched
+    let lib_contents = register_module(&read(&lib)?, day)?;
+    let solution_contents = register_day(&read(&solution)?, day)?;
+
+    let mut written = vec![module.clone(), lib.clone(), solution.clone()];
+    let mut files = vec![
+        (module, template(day)),
+        (lib, lib_contents),
+        (solution, solution_contents),
+    ];
+    if !example.exists() {
+        written.push(example.clone());
+        files.push((example, String::new()));
+    }
+    for (path, contents) in files {
+        fs::write(&path, contents)
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_module() {
+        let lib = "pub mod cli;\npub mod day01;\npub mod day03;\npub mod helpers;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "pub mod cli;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod helpers;\n"
+        );
+        assert_eq!(
+            register_module(lib, 11).unwrap(),
+            "pub mod cli;\npub mod day01;\npub mod day03;\npub mod day11;\npub mod helpers;\n"
+        );
+        assert!(register_module(lib, 3).is_err());
+        assert!(template(11).contains("pub struct Day11;"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let solution = "use crate::{day01, day03, DayResult};\n\n\
+            pub const DAYS: [Day; 2] = [\n    \
+            Day::new::<day01::Day01>(1),\n    \
+            Day::new::<day03::Day03>(3),\n];\n";
+        assert_eq!(
+            register_day(solution, 2).unwrap(),
+            "use crate::{day01, day02, day03, DayResult};\n\n\
+            pub const DAYS: [Day; 3] = [\n    \
+            Day::new::<day01::Day01>(1),\n    \
+            Day::new::<day02::Day02>(2),\n    \
+            Day::new::<day03::Day03>(3),\n];\n"
+        );
+        assert!(register_day(solution, 3).is_err());
+        let mut modules = (1..=11).map(|d| format!("day{d:0>2}")).collect::<Vec<_>>();
+        modules.push("DayResult".to_string());
+        let modules = modules.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(
+            format_import(&modules),
+            "use crate::{\n    \
+            day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, \
+            day11, DayResult,\n};"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::{fs, io};
+
+/// A single `key = value` entry of a `[day_NN]` section
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub line: usize,
+    pub day: usize,
+    pub key: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A quoted string with its escapes resolved
+    Text(String),
+    /// A list of quoted strings, e.g. `["#..#", "####"]`
+    List(Vec<String>),
+    /// Anything else, e.g. a number
+    Bare(String),
+}
+
+/// Parses the small TOML subset shared by the answers and baseline files, one section per day:
+///
+/// ```toml
+/// [day_01]
+/// part_1 = "1151792"
+/// ```
+pub fn parse(contents: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    let mut day = None;
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {msg}", i + 1);
+        if let Some(header) = line.strip_prefix("[day_") {
+            let d = header
+                .strip_suffix(']')
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| err("expected a [day_NN] header"))?;
+            day = Some(d);
+            continue;
+        }
+        let day = day.ok_or_else(|| err("entry outside of a [day_NN] section"))?;
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected key = value"))?;
+        let value = value.trim();
+        let value = if let Some(list) = value.strip_prefix('[') {
+            list.strip_suffix(']')
+                .and_then(parse_list)
+                .map(Value::List)
+                .ok_or_else(|| err("expected a list of quoted strings"))?
+        } else {
+            match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(text) => Value::Text(unescape(text)),
+                None => Value::Bare(value.to_string()),
+            }
+        };
+        entries.push(Entry {
+            line: i + 1,
+            day,
+            key: key.trim().to_string(),
+            value,
+        });
+    }
+    Ok(entries)
+}
+
+/// Splits the inside of a list into its quoted strings
+fn parse_list(list: &str) -> Option<Vec<String>> {
+    let mut items = Vec::new();
+    let mut rest = list.trim();
+    while !rest.is_empty() {
+        let quoted = rest.strip_prefix('"')?;
+        // The first quote that isn't escaped closes the string
+        let mut escaped = false;
+        let end = quoted.find(|c| {
+            let closes = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            closes
+        })?;
+        items.push(unescape(&quoted[..end]));
+        rest = quoted[end + 1..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+        } else if !rest.is_empty() {
+            return None;
+        }
+    }
+    Some(items)
+}
+
+/// Reads and parses the file at `path`, `None` if there's no file yet
+pub fn load<T>(
+    path: &Path,
+    parse: impl FnOnce(&str) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse(&contents)
+            .map(Some)
+            .map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Could not read {}: {e}", path.display())),
+    }
+}
+
+/// Resolves the `\"`, `\\` and `\n` escapes of a quoted value
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(escaped @ ('"' | '\\'))) => {
+                unescaped.push(escaped);
+                chars.next();
+            }
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Writes entries sorted by day back out, grouping consecutive entries of a day in one section
+pub fn write<'a>(entries: impl IntoIterator<Item = (usize, &'a str, Value)>) -> String {
+    let mut contents = String::new();
+    let mut day = None;
+    for (d, key, value) in entries {
+        if day != Some(d) {
+            if day.is_some() {
+                contents.push('\n');
+            }
+            writeln!(contents, "[day_{d:0>2}]").unwrap();
+            day = Some(d);
+        }
+        match value {
+            Value::Text(text) => writeln!(contents, "{key} = {}", quote(&text)).unwrap(),
+            Value::List(items) => {
+                let items = items.iter().map(|i| quote(i)).collect::<Vec<_>>();
+                writeln!(contents, "{key} = [{}]", items.join(", ")).unwrap();
+            }
+            Value::Bare(value) => writeln!(contents, "{key} = {value}").unwrap(),
+        }
+    }
+    contents
+}
+
+fn quote(text: &str) -> String {
+    let text = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{text}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let entries =
+            parse("# Comment\n\n[day_06]\npart_1 = \"5\\\"199\"\nparse = 865300\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].day, 6);
+        assert_eq!(entries[0].value, Value::Text("5\"199".to_string()));
+        assert_eq!(entries[1].key, "parse");
+        assert_eq!(entries[1].value, Value::Bare("865300".to_string()));
+        assert!(parse("part_1 = \"3\"").is_err());
+        assert!(parse("[day_xx]").is_err());
+        let text = "a\\\"b\n".to_string();
+        let written = write([(1, "part_1", Value::Text(text.clone()))]);
+        assert_eq!(parse(&written).unwrap()[0].value, Value::Text(text));
+        let list = Value::List(vec!["#.\\\"".to_string(), "a, b".to_string()]);
+        let written = write([(1, "part_1", list.clone())]);
+        assert_eq!(written, "[day_01]\npart_1 = [\"#.\\\\\\\"\", \"a, b\"]\n");
+        assert_eq!(parse(&written).unwrap()[0].value, list);
+        assert!(parse("[day_01]\npart_1 = [\"a\" \"b\"]").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::allocs::{self, Allocs};
+use crate::answer::Answer;
+use crate::cli::Parts;
+use crate::input::{self, InputError};
+use crate::logger;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day10, DayResult};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// A single day's puzzle split into the phases the runner times separately.
+pub trait Solution {
+    /// Parsed puzzle input shared by both parts
+    type Input: Clone;
+
+    /// Answers to the puzzle's example input, `None` while a part is unsolved
+    const EXAMPLE_ANSWERS: [Option<&'static str>; 2] = [None, None];
+    /// Inline example input for a part, for puzzles that give each part its own example. Parts
+    /// without one use `src/example/day_NN.txt`.
+    const EXAMPLE_INPUTS: [Option<&'static str>; 2] = [None, None];
+
+    fn parse(input: &str) -> Self::Input;
+    fn part_1(input: &mut Self::Input) -> impl Into<Answer>;
+    fn part_2(input: &mut Self::Input) -> impl Into<Answer>;
+}
+
+/// A registered day, the solution type is erased so every day fits in one table.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: usize,
+    pub run: fn(&str, Parts) -> DayResult,
+    pub profile: fn(&str, u8, usize) -> Duration,
+    pub example_answers: [Option<&'static str>; 2],
+    pub example_inputs: [Option<&'static str>; 2],
+}
+
+impl Day {
+    const fn new<S: Solution>(day: usize) -> Day {
+        Day {
+            day,
+            run: run::<S>,
+            profile: profile::<S>,
+            example_answers: S::EXAMPLE_ANSWERS,
+            example_inputs: S::EXAMPLE_INPUTS,
+        }
+    }
+
+    /// Collects the example inputs for the requested parts, each paired with the parts it feeds.
+    /// Parts sharing the bundled example file are run together from a single parse.
+    pub fn example_inputs(&self, parts: Parts) -> Result<Vec<(String, Parts)>, InputError> {
+        let mut inputs = Vec::new();
+        let mut from_file = Vec::new();
+        for part in [1, 2].into_iter().filter(|p| parts.includes(*p)) {
+            match self.example_inputs[part as usize - 1] {
+                Some(example) => inputs.push((example.to_string(), Parts::Only(part))),
+                None => from_file.push(part),
+            }
+        }
+        if !from_file.is_empty() {
+            let path = input::example_dir().join(input::file_name(self.day));
+            let parts = match from_file[..] {
+                [part] => Parts::Only(part),
+                _ => Parts::Both,
+            };
+            inputs.insert(0, (input::read_input(&path)?, parts));
+        }
+        Ok(inputs)
+    }
+}
+
+/// Every day the runner knows about. Day 9 stays out until its parser copes with the trailing
+/// newline of real inputs, it panics on them for now.
+pub const DAYS: [Day; 9] = [
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day07::Day07>(7),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day10::Day10>(10),
+];
+
+/// Parses the input and runs the requested parts, timing each phase on its own. Each part works
+/// on its own copy of the parsed input so mutations never leak between parts, the copy is made
+/// outside the timed section.
+pub fn run<S: Solution>(input: &str, parts: Parts) -> DayResult {
+    let ((mut parsed, parse_duration), parse_allocs) =
+        logger::in_phase("parse", || timed(|| S::parse(input)));
+
+    let (p1, p1_allocs) = match parts.includes(1) {
+        true => {
+            let mut data = parsed.clone();
+            let ((answer, duration), allocs) =
+                logger::in_phase("part 1", || timed(|| S::part_1(&mut data)));
+            (Some((answer.into(), duration)), allocs)
+        }
+        false => (None, None),
+    };
+    let (p2, p2_allocs) = match parts.includes(2) {
+        true => {
+            let ((answer, duration), allocs) =
+                logger::in_phase("part 2", || timed(|| S::part_2(&mut parsed)));
+            (Some((answer.into(), duration)), allocs)
+        }
+        false => (None, None),
+    };
+    (
+        Some(parse_duration),
+        p1,
+        p2,
+        [parse_allocs, p1_allocs, p2_allocs],
+    )
+}
+
+/// Parses the input once, then runs a part `iterations` times in a tight loop and returns the
+/// total time. Every iteration works on a fresh copy of the input since parts may change it, the
+/// copy shows up as its own `clone` frame in profiles.
+pub fn profile<S: Solution>(input: &str, part: u8, iterations: usize) -> Duration {
+    let parsed = S::parse(input);
+    let mut total = Duration::ZERO;
+    for _ in 0..iterations {
+        let mut data = black_box(parsed.clone());
+        let start = Instant::now();
+        match part {
+            1 => drop(black_box(S::part_1(&mut data))),
+            _ => drop(black_box(S::part_2(&mut data))),
+        }
+        total += start.elapsed();
+    }
+    total
+}
+
+/// Times a phase and counts its allocations, leaving converting the answer out of both
+#[inline(always)]
+fn timed<T>(phase: impl FnOnce() -> T) -> ((T, Duration), Option<Allocs>) {
+    allocs::measure(|| {
+        let start = Instant::now();
+        let result = phase();
+        (result, start.elapsed())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_run_parts() {
+        let input = fs::read_to_string("./src/example/day_01.txt").expect("File not found.");
+        let (parse_duration, p1, p2, _) = run::<day01::Day01>(&input, Parts::Both);
+        assert!(parse_duration.is_some());
+        assert_eq!(p1.unwrap().0, Answer::Integer(11));
+        assert_eq!(p2.unwrap().0, Answer::Integer(31));
+        let (_, p1, p2, _) = run::<day01::Day01>(&input, Parts::Only(2));
+        assert!(p1.is_none());
+        assert_eq!(p2.unwrap().0, Answer::Integer(31));
+    }
+
+    #[test]
+    fn test_profile() {
+        let input = fs::read_to_string("./src/example/day_01.txt").expect("File not found.");
+        assert!(profile::<day01::Day01>(&input, 2, 3) > Duration::ZERO);
+        assert_eq!(profile::<day01::Day01>(&input, 1, 0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_example_inputs() {
+        // Day 3 gives each part its own example
+        let inputs = DAYS[2].example_inputs(Parts::Both).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert!(inputs.iter().all(|(_, parts)| *parts != Parts::Both));
+        let inputs = DAYS[0].example_inputs(Parts::Both).unwrap();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].1, Parts::Both);
+    }
+
+    #[test]
+    fn test_registry_order() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+}
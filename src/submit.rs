@@ -0,0 +1,402 @@
+use crate::answer::Answer;
+use crate::cli::Parts;
+use crate::client::Client;
+use crate::input::{self, InputSet};
+use crate::sections::{self, Value};
+use crate::solution::Day;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+/// Default submission history file name, kept next to the inputs the answers were computed from
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+/// The website's reply to a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint which way, e.g. for text answers
+    Wrong,
+    /// The answer was submitted before the cooldown of the last one ran out and wasn't checked
+    TooSoon,
+    /// The part was already solved on the website, the submitted answer wasn't checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    const RECORDED: [Verdict; 5] = [
+        Verdict::Correct,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+        Verdict::Wrong,
+        Verdict::AlreadySolved,
+    ];
+
+    /// Suffix of the history key, attempts that weren't checked because they came too soon aren't
+    /// recorded
+    fn key(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too_high"),
+            Verdict::TooLow => Some("too_low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::AlreadySolved => Some("already_solved"),
+            Verdict::TooSoon => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooSoon => write!(f, "submitted too soon"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Reads the verdict and the cooldown before the next attempt from the page returned for a
+/// submission
+pub fn parse_response(html: &str) -> Result<(Verdict, Option<Duration>), String> {
+    let article = html
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    // Only the text matters, the tags around it are dropped
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Ok((Verdict::Correct, None))
+    } else if text.contains("You gave an answer too recently") {
+        Ok((Verdict::TooSoon, Some(left_to_wait(&text)?)))
+    } else if text.contains("Did you already complete it?") {
+        Ok((Verdict::AlreadySolved, None))
+    } else if text.contains("That's not the right answer") {
+        let verdict = if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        Ok((verdict, Some(wait_minutes(&text)?)))
+    } else {
+        Err(format!("Unexpected response to the submission: {text}"))
+    }
+}
+
+/// Reads `You have 1m 12s left to wait`
+fn left_to_wait(text: &str) -> Result<Duration, String> {
+    let left = text
+        .split_once("You have ")
+        .and_then(|(_, rest)| rest.split_once(" left to wait"))
+        .map(|(left, _)| left)
+        .ok_or_else(|| format!("No cooldown found in: {text}"))?;
+    let mut secs = 0;
+    for part in left.split_whitespace() {
+        let (n, unit) = part.split_at(part.len() - 1);
+        let n = n
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid cooldown '{left}'"))?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return Err(format!("Invalid cooldown '{left}'")),
+        };
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Reads `please wait one minute` or `please wait 5 minutes`, wrong answers are always followed
+/// by a cooldown so a minute is assumed when none is given
+fn wait_minutes(text: &str) -> Result<Duration, String> {
+    let Some((_, rest)) = text.split_once("please wait ") else {
+        return Ok(Duration::from_secs(60));
+    };
+    let minutes = match rest.split_whitespace().next() {
+        Some("one") => 1,
+        Some(n) => n
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid cooldown '{n}'"))?,
+        None => 1,
+    };
+    Ok(Duration::from_secs(minutes * 60))
+}
+
+/// Every answer submitted so far and when the next submission of a day is allowed, one
+/// `[day_NN]` section per day:
+///
+/// ```toml
+/// [day_06]
+/// part_2_too_high = 1934
+/// part_2_correct = 1831
+/// retry_after = 1733466120
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    /// Day, part, answer and verdict of every attempt in the order they were made
+    attempts: Vec<(usize, u8, Answer, Verdict)>,
+    /// Unix time in seconds a day can be submitted again at
+    retry_after: BTreeMap<usize, u64>,
+}
+
+impl History {
+    /// Loads the history at `path`, `None` if nothing has been submitted yet
+    pub fn load(path: &Path) -> Result<Option<History>, String> {
+        sections::load(path, History::parse)
+    }
+
+    pub fn parse(contents: &str) -> Result<History, String> {
+        let mut history = History::default();
+        for entry in sections::parse(contents)? {
+            let err = |msg: &str| format!("line {}: {msg}", entry.line);
+            if entry.key == "retry_after" {
+                let Value::Bare(secs) = entry.value else {
+                    return Err(err("expected a unix timestamp"));
+                };
+                let secs = secs.parse().map_err(|_| err("expected a unix timestamp"))?;
+                history.retry_after.insert(entry.day, secs);
+                continue;
+            }
+            let attempt = Verdict::RECORDED.iter().find_map(|v| {
+                let (part, key) = entry.key.strip_prefix("part_")?.split_once('_')?;
+                let part = part.parse().ok().filter(|p| [1, 2].contains(p))?;
+                (v.key() == Some(key)).then_some((part, *v))
+            });
+            let Some((part, verdict)) = attempt else {
+                return Err(err(&format!("unknown key '{}'", entry.key)));
+            };
+            let answer = Answer::from_value(entry.value).map_err(|e| err(&e))?;
+            history.attempts.push((entry.day, part, answer, verdict));
+        }
+        Ok(history)
+    }
+
+    /// Explains why `answer` shouldn't be submitted: the day is still cooling down, the part is
+    /// solved already or the answer is known to be wrong
+    pub fn check(&self, day: usize, part: u8, answer: &Answer, now: u64) -> Result<(), String> {
+        if let Some(retry_after) = self.retry_after.get(&day).filter(|r| **r > now) {
+            return Err(format!(
+                "Day {day} can't be submitted again for another {}s",
+                retry_after - now
+            ));
+        }
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|(d, p, _, _)| *d == day && *p == part);
+        for (_, _, submitted, verdict) in attempts {
+            let bound = match (answer, submitted) {
+                (Answer::Integer(a), Answer::Integer(s)) => Some((a, s)),
+                _ => None,
+            };
+            match (verdict, bound) {
+                (Verdict::Correct, _) => {
+                    return Err(format!(
+                        "Part {part} of day {day} is already solved with {submitted}"
+                    ))
+                }
+                (Verdict::AlreadySolved, _) => {
+                    return Err(format!("Part {part} of day {day} is already solved"))
+                }
+                _ if submitted == answer => {
+                    return Err(format!("{answer} was already submitted and is {verdict}"))
+                }
+                (Verdict::TooHigh, Some((a, s))) if a > s => {
+                    return Err(format!("{answer} is too high, {submitted} already was"))
+                }
+                (Verdict::TooLow, Some((a, s))) if a < s => {
+                    return Err(format!("{answer} is too low, {submitted} already was"))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the verdict on an answer along with the cooldown that follows it
+    pub fn record(
+        &mut self,
+        day: usize,
+        part: u8,
+        answer: &Answer,
+        verdict: Verdict,
+        wait: Option<Duration>,
+        now: u64,
+    ) {
+        if verdict.key().is_some() {
+            self.attempts.push((day, part, answer.clone(), verdict));
+        }
+        if let Some(wait) = wait {
+            self.retry_after.insert(day, now + wait.as_secs());
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    fn to_toml(&self) -> String {
+        let mut entries = self
+            .attempts
+            .iter()
+            .map(|(day, part, answer, verdict)| {
+                let key = format!("part_{part}_{}", verdict.key().unwrap_or_default());
+                (*day, key, answer.to_value())
+            })
+            .chain(self.retry_after.iter().map(|(day, secs)| {
+                (
+                    *day,
+                    "retry_after".to_string(),
+                    Value::Bare(secs.to_string()),
+                )
+            }))
+            .collect::<Vec<_>>();
+        // Stable, so the attempts of a day keep their order
+        entries.sort_by_key(|(day, _, _)| *day);
+        sections::write(
+            entries
+                .iter()
+                .map(|(day, key, value)| (*day, key.as_str(), value.clone())),
+        )
+    }
+}
+
+/// The submission history for an input directory
+pub fn history_path(dir: &Path) -> PathBuf {
+    dir.join(HISTORY_FILE)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Solves a part of a day on its input in `set` and submits the answer, unless the history already
+/// knows the verdict or the day is still cooling down. The client is only built when the answer
+/// is actually submitted.
+pub fn submit(
+    d: &Day,
+    part: u8,
+    set: &InputSet,
+    client: impl FnOnce() -> Result<Client, String>,
+) -> Result<(Answer, Verdict), String> {
+    let input = input::read_input(&set.path(d.day)).map_err(|e| e.to_string())?;
+    let (_, p1, p2, _) = (d.run)(&input, Parts::Only(part));
+    let (answer, _) = match part {
+        1 => p1,
+        _ => p2,
+    }
+    .ok_or(format!("Part {part} of day {} returned no answer", d.day))?;
+    if let Answer::Grid(_) = answer {
+        return Err(format!(
+            "Grid answers have to be read and submitted by hand:\n{answer}"
+        ));
+    }
+
+    let path = history_path(&set.dir);
+    let mut history = History::load(&path)?.unwrap_or_default();
+    history.check(d.day, part, &answer, unix_now())?;
+    let response = client()?.submit(d.day, part, &answer)?;
+    let (verdict, wait) = parse_response(&response)?;
+    history.record(d.day, part, &answer, verdict, wait, unix_now());
+    history
+        .save(&path)
+        .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    Ok((answer, verdict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer! You are <span class=\"day-success\">one gold star</span> \
+                closer."
+            )),
+            Ok((Verdict::Correct, None))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+                you're using the full input data. Please wait one minute before trying again. \
+                <a href=\"/2024/day/6\">[Return to Day 6]</a>"
+            )),
+            Ok((Verdict::TooHigh, Some(Duration::from_secs(60))))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer. Because you have guessed incorrectly 4 times on \
+                this puzzle, please wait 5 minutes before trying again."
+            )),
+            Ok((Verdict::Wrong, Some(Duration::from_secs(300))))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have 1m 12s left to wait."
+            )),
+            Ok((Verdict::TooSoon, Some(Duration::from_secs(72))))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Ok((Verdict::AlreadySolved, None))
+        );
+        assert!(parse_response("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = History::default();
+        let wait = |secs| Some(Duration::from_secs(secs));
+        history.record(3, 1, &Answer::from("ab"), Verdict::Wrong, wait(60), 100);
+        history.record(3, 1, &Answer::from("ac"), Verdict::TooSoon, wait(30), 110);
+        history.record(6, 2, &Answer::from(1934), Verdict::TooHigh, None, 0);
+        history.record(6, 2, &Answer::from(1200), Verdict::TooLow, None, 0);
+        history.record(6, 1, &Answer::from(5199), Verdict::Correct, None, 0);
+        history.record(7, 2, &Answer::from("12"), Verdict::AlreadySolved, None, 0);
+
+        assert!(history.check(6, 1, &Answer::from(5200), 0).is_err());
+        assert!(history.check(6, 2, &Answer::from(1934), 0).is_err());
+        assert!(history.check(6, 2, &Answer::from(2000), 0).is_err());
+        assert!(history.check(6, 2, &Answer::from(1100), 0).is_err());
+        assert!(history.check(6, 2, &Answer::from(1831), 0).is_ok());
+        assert!(history.check(3, 1, &Answer::from("ad"), 139).is_err());
+        assert!(history.check(3, 1, &Answer::from("ad"), 140).is_ok());
+        assert!(history.check(3, 1, &Answer::from("ab"), 140).is_err());
+        assert!(history.check(7, 2, &Answer::from(13), 0).is_err());
+
+        let toml = history.to_toml();
+        assert!(toml.starts_with("[day_03]\npart_1_wrong = \"ab\"\nretry_after = 140\n"));
+        assert!(toml.contains("[day_06]\npart_2_too_high = 1934\npart_2_too_low = 1200\n"));
+        assert!(toml.ends_with("[day_07]\npart_2_already_solved = \"12\"\n"));
+        assert_eq!(History::parse(&toml).unwrap(), history);
+        assert!(History::parse("[day_06]\npart_3_correct = \"1\"").is_err());
+    }
+}
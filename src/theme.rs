@@ -0,0 +1,205 @@
+use colored::{Color, ColoredString, Colorize};
+
+/// Environment variable with the theme used when `--theme` isn't given
+pub const THEME_VAR: &str = "AOC_THEME";
+
+const UNDERLINE: &str = "~~~~~~~~~~~~~~~~~~~~~";
+
+/// How the title of a day is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Banner {
+    /// The title over a line of tildes
+    Underline,
+    /// The title on a single line
+    Line,
+}
+
+/// Look of the output of every day, festive red and green banners by default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Banner colors cycled through by day, nothing is colored at all when empty
+    pub colors: Vec<Color>,
+    /// Decoration on both sides of the banner title
+    pub emoji: Option<String>,
+    pub banner: Banner,
+    /// Sticks to ASCII for everything else, e.g. check marks
+    pub ascii: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::festive()
+    }
+}
+
+impl Theme {
+    /// Alternating red and green banners between Christmas trees
+    pub fn festive() -> Theme {
+        Theme {
+            colors: vec![Color::BrightRed, Color::BrightGreen],
+            emoji: Some("🎄".to_string()),
+            banner: Banner::Underline,
+            ascii: false,
+        }
+    }
+
+    /// Uncolored ASCII, for logs and terminals without emoji
+    pub fn plain() -> Theme {
+        Theme {
+            colors: Vec::new(),
+            emoji: None,
+            banner: Banner::Underline,
+            ascii: true,
+        }
+    }
+
+    /// Parses a comma-separated list of a preset, `festive` or `plain`, and `key=value` overrides,
+    /// e.g. `festive,colors=blue:bright_cyan,emoji=⭐,banner=line`
+    pub fn parse(spec: &str) -> Result<Theme, String> {
+        let mut theme = Theme::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some(("colors", "none")) => theme.colors.clear(),
+                Some(("colors", colors)) => {
+                    theme.colors = colors
+                        .split(':')
+                        .map(|c| {
+                            c.replace(['_', '-'], " ")
+                                .parse()
+                                .map_err(|_| format!("Invalid color '{c}' in '{spec}'"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                Some(("emoji", "none")) => theme.emoji = None,
+                Some(("emoji", emoji)) => theme.emoji = Some(emoji.to_string()),
+                Some(("banner", "underline")) => theme.banner = Banner::Underline,
+                Some(("banner", "line")) => theme.banner = Banner::Line,
+                Some(("ascii", ascii)) => {
+                    theme.ascii = ascii
+                        .parse()
+                        .map_err(|_| format!("Invalid ascii '{ascii}', expected true or false"))?;
+                }
+                None if directive == "festive" => theme = Theme::festive(),
+                None if directive == "plain" => theme = Theme::plain(),
+                _ => return Err(format!("Invalid theme directive '{directive}' in '{spec}'")),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Picks the theme from the command line, then the environment, falling back to plain ASCII
+    /// when neither is given and stdout isn't a terminal. The colors are dropped when `NO_COLOR`
+    /// is set.
+    pub fn resolve(
+        arg: Option<Theme>,
+        env_spec: Option<String>,
+        tty: bool,
+        no_color: bool,
+    ) -> Result<Theme, String> {
+        let mut theme = match (arg, env_spec) {
+            (Some(theme), _) => theme,
+            (None, Some(spec)) => Theme::parse(&spec).map_err(|e| format!("${THEME_VAR}: {e}"))?,
+            (None, None) if !tty => Theme::plain(),
+            (None, None) => Theme::default(),
+        };
+        if no_color {
+            theme.colors.clear();
+        }
+        Ok(theme)
+    }
+
+    /// Color of a day's banner, the colors take turns from day 1 on
+    pub fn color(&self, day: usize) -> Option<Color> {
+        let len = self.colors.len();
+        (len > 0).then(|| self.colors[(day + len - 1) % len])
+    }
+
+    /// The title of a day laid out as a banner, in the day's color
+    pub fn banner(&self, day: usize, title: &str) -> ColoredString {
+        let banner = match (self.banner, &self.emoji) {
+            (Banner::Underline, Some(emoji)) => format!("{emoji}{title} {emoji}\n{UNDERLINE}"),
+            (Banner::Underline, None) => format!("{title}\n{UNDERLINE}"),
+            (Banner::Line, Some(emoji)) => format!("{emoji} {title} {emoji}"),
+            (Banner::Line, None) => format!("== {title} =="),
+        };
+        match self.color(day) {
+            Some(color) => banner.color(color),
+            None => banner.normal(),
+        }
+    }
+
+    pub fn pass_mark(&self) -> &'static str {
+        if self.ascii {
+            "ok"
+        } else {
+            "✓"
+        }
+    }
+
+    pub fn fail_mark(&self) -> &'static str {
+        if self.ascii {
+            "x"
+        } else {
+            "✗"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Theme::parse("").unwrap(), Theme::festive());
+        assert_eq!(Theme::parse("festive,plain").unwrap(), Theme::plain());
+        let theme = Theme::parse("colors=blue:bright_cyan, emoji=⭐, banner=line").unwrap();
+        assert_eq!(theme.colors, vec![Color::Blue, Color::BrightCyan]);
+        assert_eq!(theme.emoji.as_deref(), Some("⭐"));
+        assert_eq!(theme.banner, Banner::Line);
+        assert!(!theme.ascii);
+        assert!(Theme::parse("colors=plaid").is_err());
+        assert!(Theme::parse("banner=box").is_err());
+        assert!(Theme::parse("gloomy").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let line = Theme::parse("banner=line").unwrap();
+        let env = Some("plain".to_string());
+        assert_eq!(
+            Theme::resolve(None, None, true, false),
+            Ok(Theme::festive())
+        );
+        assert_eq!(
+            Theme::resolve(None, env.clone(), true, false),
+            Ok(Theme::plain())
+        );
+        assert_eq!(
+            Theme::resolve(Some(line.clone()), env, true, false),
+            Ok(line.clone())
+        );
+        assert_eq!(Theme::resolve(None, None, false, false), Ok(Theme::plain()));
+        // A theme that was asked for is kept when piped, e.g. into `less -R`
+        assert_eq!(
+            Theme::resolve(Some(line.clone()), None, false, false),
+            Ok(line)
+        );
+        let no_color = Theme::resolve(None, None, true, true).unwrap();
+        assert!(no_color.colors.is_empty() && no_color.emoji.is_some());
+        assert!(Theme::resolve(None, Some("gloomy".to_string()), true, false).is_err());
+
+        let theme = Theme::festive();
+        assert_eq!(
+            &*theme.banner(6, "Day 6 (1ms)"),
+            "🎄Day 6 (1ms) 🎄\n~~~~~~~~~~~~~~~~~~~~~"
+        );
+        assert_eq!(
+            &*Theme::plain().banner(6, "Day 6"),
+            "Day 6\n~~~~~~~~~~~~~~~~~~~~~"
+        );
+        assert_eq!(theme.color(1), Some(Color::BrightRed));
+        assert_eq!(theme.color(6), Some(Color::BrightGreen));
+        assert_eq!(Theme::plain().color(6), None);
+    }
+}
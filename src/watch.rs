@@ -0,0 +1,211 @@
+use crate::answer::Answer;
+use crate::baseline;
+use crate::cli::Args;
+use crate::input::{self, InputSet};
+use crate::output::{self, Format};
+use crate::runner::{self, DayRun, DayStatus, Solved};
+use crate::solution::Day;
+use crate::theme::Theme;
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+/// How often the watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Polls the modification times of a set of files, missing files count as unmodified until
+/// they show up
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Watcher {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    /// The files modified since the last call
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in &mut self.files {
+            let modified = modified(path);
+            if modified != *last {
+                *last = modified;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Describes how the answers and timings of a re-run differ from the run before it
+pub fn changes(previous: &Solved, current: &Solved, threshold: f64) -> Vec<String> {
+    let mut changes = Vec::new();
+    for p in &current.parts {
+        let before = previous.parts.iter().find(|b| b.part == p.part);
+        match before {
+            Some(before) if before.answer == p.answer => {
+                changes.push(format!("Part {}: {} (unchanged)", p.part, p.answer))
+            }
+            Some(before) => changes.push(format!(
+                "Part {}: {} -> {}",
+                p.part, before.answer, p.answer
+            )),
+            None => changes.push(format!("Part {}: {} (new)", p.part, p.answer)),
+        }
+    }
+    changes.extend(
+        baseline::compare(&previous.phases, &current.phases, threshold)
+            .iter()
+            .map(|delta| delta.to_string()),
+    );
+    changes
+}
+
+/// The source file of a day, its tests are run when it or its example changes
+fn source_path(day: usize) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{day:0>2}.rs"))
+}
+
+/// Runs the day's tests through cargo, as they need the changed source compiled first
+fn run_tests(day: usize) {
+    println!("{}", format!("Running the tests of day {day}").white());
+    let status = Command::new(env!("CARGO"))
+        .args(["test", "--lib", &format!("day{day:0>2}::")])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status();
+    match status {
+        Ok(status) if status.success() => println!("{}\n", "Tests passed".green()),
+        Ok(_) => println!("{}\n", "Tests failed".red()),
+        Err(e) => eprintln!("Could not run cargo test: {e}\n"),
+    }
+}
+
+/// Re-runs a day whenever its input changes, and its tests whenever its source or example
+/// changes, until the process is interrupted.
+pub fn watch(
+    d: Day,
+    set: &InputSet,
+    args: &Arc<Args>,
+    theme: &Theme,
+    expected: Option<[Option<Answer>; 2]>,
+    mut previous: Option<Solved>,
+) -> ! {
+    let input = if args.example {
+        input::example_dir().join(input::file_name(d.day))
+    } else {
+        args.input.clone().unwrap_or_else(|| set.path(d.day))
+    };
+    let example = input::example_dir().join(input::file_name(d.day));
+    let source = source_path(d.day);
+    let mut watcher = Watcher::new([input.clone(), example.clone(), source.clone()]);
+    eprintln!(
+        "Watching {}, {} and {} for changes",
+        input.display(),
+        example.display(),
+        source.display()
+    );
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let changed = watcher.changed();
+        if changed.contains(&input) {
+            println!(
+                "{}",
+                format!("{} changed, re-running day {}", input.display(), d.day).white()
+            );
+            let status = match runner::load_inputs(&d, set, args) {
+                Ok(inputs) => {
+                    let (expected, args) = (expected.clone(), Arc::clone(args));
+                    runner::isolate(d.day, args.timeout, move || {
+                        runner::run_day(&d, &inputs, expected, None, &args)
+                    })
+                    .map_or_else(DayStatus::Interrupted, |s| DayStatus::Solved(Box::new(s)))
+                }
+                Err(e) => DayStatus::Failed(e),
+            };
+            let run = DayRun {
+                day: d.day,
+                profile: set.profile.clone(),
+                status,
+            };
+            match (&run.status, args.format) {
+                (DayStatus::Solved(_), Format::Pretty) => output::print_day(&run, theme),
+                _ => print!("{}", output::plain(std::slice::from_ref(&run))),
+            }
+            if let (DayStatus::Solved(solved), Some(previous)) = (&run.status, &previous) {
+                println!("Since the last run:");
+                for change in changes(previous, solved, args.threshold) {
+                    println!("{change}");
+                }
+                println!();
+            }
+            if let DayStatus::Solved(solved) = run.status {
+                previous = Some(*solved);
+            }
+        }
+        if changed.contains(&source) || changed.contains(&example) {
+            run_tests(d.day);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Check;
+    use std::fs::File;
+
+    fn solved(answers: &[&str], part_1: u64) -> Solved {
+        let phases = [None, Some(Duration::from_micros(part_1)), None];
+        let parts = answers
+            .iter()
+            .map(|a| (Answer::parse(a), Check::Unchecked))
+            .collect();
+        runner::solved(phases, parts)
+    }
+
+    #[test]
+    fn test_changes() {
+        let changes = changes(&solved(&["41"], 100), &solved(&["41", "6"], 150), 10.0);
+        assert_eq!(changes[0], "Part 1: 41 (unchanged)");
+        assert_eq!(changes[1], "Part 2: 6 (new)");
+        assert!(changes[2].starts_with("Part 1") && changes[2].ends_with("+50.0%"));
+        let changes = super::changes(&solved(&["41"], 100), &solved(&["42"], 100), 10.0);
+        assert_eq!(changes[0], "Part 1: 41 -> 42");
+    }
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc_watch_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new([path.clone()]);
+        assert!(watcher.changed().is_empty());
+        fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+        let later = SystemTime::now() + Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        fs::remove_file(&path).unwrap();
+    }
+}